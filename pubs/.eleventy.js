@@ -17,6 +17,12 @@ export default function (eleventyConfig) {
 
     eleventyConfig.addPassthroughCopy('assets')
 
+    // Figures drawn by `--figures`, referenced by day and their stable name
+    eleventyConfig.addShortcode('figure', function (day, name, alt) {
+        const src = eleventyConfig.getFilter('url')(`/assets/images/day_${day}/${name}.svg`)
+        return `<figure class="puzzle-figure"><img src="${src}" alt="${alt}"/></figure>`
+    })
+
     return {
         passthroughFileCopy:    true,
         markdownTemplateEngine: 'njk',
@@ -0,0 +1,320 @@
+//! An on-disk cache of puzzle answers, so that re-running slow solutions (e.g. [`crate::day_6`] part 2) can return
+//! instantly.
+//!
+//! Answers are keyed by day, part, a hash of the puzzle input, and the version string the day is registered with in
+//! [`crate::main`]. Bumping the version invalidates the cached answers for that day. Answers can be marked as
+//! confirmed once they have been accepted by the Advent of Code site, and [`AnswerCache::warnings`] reports when the
+//! input has changed since then, or a new version of the solver disagrees with a confirmed answer.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The default location of the cache file
+pub const CACHE_PATH: &str = "res/answer_cache.json";
+
+/// The fields an answer is cached under
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct CacheKey {
+    pub day: u8,
+    pub part: u8,
+    pub input_hash: String,
+    pub version: String,
+}
+
+impl CacheKey {
+    pub fn new(day: u8, part: u8, input: &str, version: &str) -> CacheKey {
+        CacheKey {
+            day,
+            part,
+            input_hash: hash_input(input),
+            version: version.to_string(),
+        }
+    }
+}
+
+/// A cached answer and whether it has been confirmed as correct
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct CachedAnswer {
+    #[serde(flatten)]
+    pub key: CacheKey,
+    pub answer: String,
+    pub confirmed: bool,
+    /// How long the solution took the last time it was run rather than taken from the cache, in microseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_us: Option<u64>,
+}
+
+/// The collection of cached answers, and the file it is persisted to
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct AnswerCache {
+    path: PathBuf,
+    answers: Vec<CachedAnswer>,
+}
+
+/// SHA-256 of the puzzle input as a hex string. This needs to be stable between builds, so `std`'s `DefaultHasher`
+/// can't be used.
+pub fn hash_input(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input.as_bytes()))
+}
+
+impl AnswerCache {
+    /// An empty cache that will be saved to `path`
+    pub fn new(path: impl AsRef<Path>) -> AnswerCache {
+        AnswerCache {
+            path: path.as_ref().to_path_buf(),
+            answers: Vec::new(),
+        }
+    }
+
+    /// Load the cache from `path`, a missing file is treated as an empty cache
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<AnswerCache> {
+        let mut cache = AnswerCache::new(path);
+        match fs::read_to_string(&cache.path) {
+            Ok(contents) => {
+                cache.answers = serde_json::from_str(&contents)?;
+                Ok(cache)
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(cache),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Write the cache back to the file it was loaded from
+    pub fn save(&self) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&self.answers)?)
+    }
+
+    /// The cached answer for the exact day, part, input and version, if there is one
+    pub fn get(&self, key: &CacheKey) -> Option<&CachedAnswer> {
+        self.answers.iter().find(|cached| &cached.key == key)
+    }
+
+    /// Store an answer, replacing any previous answer for the same key. A previous confirmation and duration are only
+    /// kept if the answer hasn't changed.
+    pub fn insert(&mut self, key: CacheKey, answer: String) {
+        let unchanged = self.get(&key).filter(|cached| cached.answer == answer);
+        let confirmed = unchanged.is_some_and(|cached| cached.confirmed);
+        let duration_us = unchanged.and_then(|cached| cached.duration_us);
+
+        self.answers.retain(|cached| cached.key != key);
+        self.answers.push(CachedAnswer {
+            key,
+            answer,
+            confirmed,
+            duration_us,
+        });
+    }
+
+    /// Record how long the solution for a key took to run
+    pub fn record_duration(&mut self, key: &CacheKey, duration: Duration) {
+        if let Some(cached) = self.answers.iter_mut().find(|cached| &cached.key == key) {
+            cached.duration_us = Some(duration.as_micros() as u64);
+        }
+    }
+
+    /// The answer confirmed for a day and part with this input, by any version of the solver
+    pub fn confirmed_answer(&self, day: u8, part: u8, input: &str) -> Option<&str> {
+        let input_hash = hash_input(input);
+        self.answers
+            .iter()
+            .find(|cached| {
+                cached.confirmed
+                    && cached.key.day == day
+                    && cached.key.part == part
+                    && cached.key.input_hash == input_hash
+            })
+            .map(|cached| cached.answer.as_str())
+    }
+
+    /// Mark the answer for a key as accepted by the puzzle site. Returns false if there is no answer for that key.
+    pub fn confirm(&mut self, key: &CacheKey) -> bool {
+        match self.answers.iter_mut().find(|cached| &cached.key == key) {
+            Some(cached) => {
+                cached.confirmed = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Checks a freshly calculated answer against any confirmed answers for the same day and part, returning
+    /// human-readable warnings if:
+    ///
+    /// - The input hash has changed since an answer was confirmed
+    /// - A confirmed answer for the same input differs from the new answer, e.g. because a change to the solver
+    ///   introduced a regression
+    pub fn warnings(&self, key: &CacheKey, answer: &str) -> Vec<String> {
+        let confirmed: Vec<&CachedAnswer> = self
+            .answers
+            .iter()
+            .filter(|cached| {
+                cached.confirmed && cached.key.day == key.day && cached.key.part == key.part
+            })
+            .collect();
+
+        let mut warnings = Vec::new();
+
+        if !confirmed.is_empty()
+            && confirmed
+                .iter()
+                .all(|cached| cached.key.input_hash != key.input_hash)
+        {
+            warnings.push(format!(
+                "The input for day {} has changed since the answer to part {} was confirmed",
+                key.day, key.part
+            ));
+        }
+
+        confirmed
+            .iter()
+            .filter(|cached| cached.key.input_hash == key.input_hash && cached.answer != answer)
+            .for_each(|cached| {
+                warnings.push(format!(
+                    "Day {} part {} gave {}, but {} was confirmed for this input with version {}",
+                    key.day, key.part, answer, cached.answer, cached.key.version
+                ))
+            });
+
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer_cache::*;
+    use std::env::temp_dir;
+
+    fn key(part: u8, input: &str, version: &str) -> CacheKey {
+        CacheKey::new(6, part, input, version)
+    }
+
+    #[test]
+    fn can_hash_input() {
+        assert_eq!(
+            hash_input("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_ne!(hash_input("abc\n"), hash_input("abc"));
+    }
+
+    #[test]
+    fn can_get_cached_answers() {
+        let mut cache = AnswerCache::new("unused.json");
+        cache.insert(key(1, "input", "1"), "41".to_string());
+        cache.insert(key(2, "input", "1"), "6".to_string());
+
+        assert_eq!(
+            cache.get(&key(1, "input", "1")).map(|c| c.answer.as_str()),
+            Some("41")
+        );
+        assert_eq!(
+            cache.get(&key(2, "input", "1")).map(|c| c.answer.as_str()),
+            Some("6")
+        );
+        assert_eq!(cache.get(&key(1, "input", "2")), None);
+        assert_eq!(cache.get(&key(1, "changed", "1")), None);
+
+        cache.insert(key(1, "input", "1"), "42".to_string());
+        assert_eq!(
+            cache.get(&key(1, "input", "1")).map(|c| c.answer.as_str()),
+            Some("42")
+        );
+        assert_eq!(cache.answers.len(), 2);
+    }
+
+    #[test]
+    fn can_confirm_answers() {
+        let mut cache = AnswerCache::new("unused.json");
+        assert!(!cache.confirm(&key(1, "input", "1")));
+
+        cache.insert(key(1, "input", "1"), "41".to_string());
+        assert!(cache.confirm(&key(1, "input", "1")));
+        assert!(cache.get(&key(1, "input", "1")).unwrap().confirmed);
+
+        cache.insert(key(1, "input", "1"), "41".to_string());
+        assert!(cache.get(&key(1, "input", "1")).unwrap().confirmed);
+
+        cache.insert(key(1, "input", "1"), "40".to_string());
+        assert!(!cache.get(&key(1, "input", "1")).unwrap().confirmed);
+    }
+
+    #[test]
+    fn can_find_confirmed_answers_for_any_version() {
+        let mut cache = AnswerCache::new("unused.json");
+        cache.insert(key(1, "input", "1"), "41".to_string());
+        assert_eq!(cache.confirmed_answer(6, 1, "input"), None);
+
+        cache.confirm(&key(1, "input", "1"));
+        cache.insert(key(1, "input", "2"), "40".to_string());
+
+        assert_eq!(cache.confirmed_answer(6, 1, "input"), Some("41"));
+        assert_eq!(cache.confirmed_answer(6, 2, "input"), None);
+        assert_eq!(cache.confirmed_answer(6, 1, "changed"), None);
+    }
+
+    #[test]
+    fn can_record_durations() {
+        let mut cache = AnswerCache::new("unused.json");
+        cache.insert(key(1, "input", "1"), "41".to_string());
+        assert_eq!(cache.get(&key(1, "input", "1")).unwrap().duration_us, None);
+
+        cache.record_duration(&key(1, "input", "1"), Duration::from_millis(12));
+        cache.insert(key(1, "input", "1"), "41".to_string());
+        assert_eq!(
+            cache.get(&key(1, "input", "1")).unwrap().duration_us,
+            Some(12_000)
+        );
+
+        cache.insert(key(1, "input", "1"), "40".to_string());
+        assert_eq!(cache.get(&key(1, "input", "1")).unwrap().duration_us, None);
+    }
+
+    #[test]
+    fn can_warn_about_changes_since_confirmation() {
+        let mut cache = AnswerCache::new("unused.json");
+        cache.insert(key(1, "input", "1"), "41".to_string());
+
+        assert!(cache.warnings(&key(1, "changed", "1"), "41").is_empty());
+
+        cache.confirm(&key(1, "input", "1"));
+
+        assert!(cache.warnings(&key(1, "input", "1"), "41").is_empty());
+        assert!(cache.warnings(&key(1, "input", "2"), "41").is_empty());
+        assert!(cache.warnings(&key(2, "changed", "1"), "6").is_empty());
+
+        assert_eq!(
+            cache.warnings(&key(1, "changed", "1"), "41"),
+            vec!["The input for day 6 has changed since the answer to part 1 was confirmed"]
+        );
+        assert_eq!(
+            cache.warnings(&key(1, "input", "2"), "40"),
+            vec!["Day 6 part 1 gave 40, but 41 was confirmed for this input with version 1"]
+        );
+    }
+
+    #[test]
+    fn can_save_and_load_cache() {
+        let path = temp_dir().join(format!("aoc-answer-cache-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(AnswerCache::load(&path).unwrap(), AnswerCache::new(&path));
+
+        let mut cache = AnswerCache::new(&path);
+        cache.insert(key(1, "input", "1"), "41".to_string());
+        cache.insert(key(2, "input", "1"), "6".to_string());
+        cache.confirm(&key(2, "input", "1"));
+        cache.record_duration(&key(2, "input", "1"), Duration::from_micros(1500));
+        cache.save().unwrap();
+
+        assert_eq!(AnswerCache::load(&path).unwrap(), cache);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
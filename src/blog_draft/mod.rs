@@ -0,0 +1,494 @@
+//! Draft a blog post for a day from its source file, as the write-ups mostly re-explain what is already in the docs.
+//!
+//! The draft is made from the `//!` module docs, followed by each documented function in the order it appears in the
+//! file, with its `///` docs and its source in a code block. The tests module is skipped. Rustdoc links like
+//! ``[`parse_input`]`` become inline code, as the blog can't resolve them.
+//!
+//! Drafts start with a marker comment holding a checksum of the generated text. A post is only replaced if it is
+//! missing, only has front matter (as written by `bootstrap_day`), or is a draft that still matches its checksum, so
+//! a post that has been edited by hand is never overwritten.
+
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const MARKER_PREFIX: &str = "<!-- Draft generated from ";
+
+/// What happened when drafting a post
+#[derive(Eq, PartialEq, Debug)]
+pub enum DraftOutcome {
+    Written(PathBuf),
+    /// The post has been changed since it was scaffolded or drafted, so was left alone
+    EditedByHand(PathBuf),
+}
+
+/// A function with its docs, as found in the source file
+#[derive(Eq, PartialEq, Debug)]
+struct DocumentedFunction {
+    name: String,
+    docs: String,
+    source: String,
+}
+
+/// The text of the leading `//!` comments
+fn module_docs(source: &str) -> String {
+    source
+        .lines()
+        .take_while(|line| line.starts_with("//!"))
+        .map(|line| doc_line(line, "//!"))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+fn doc_line<'a>(line: &'a str, prefix: &str) -> &'a str {
+    let text = line.trim().trim_start_matches(prefix);
+    text.strip_prefix(' ').unwrap_or(text)
+}
+
+/// The title from the module docs' first line, e.g. `[Advent of Code - Day 22: _Monkey Market_](...)`
+fn title(module_docs: &str) -> Option<String> {
+    let pattern = Regex::new(r"Day \d+: _(?<title>[^_]+)_").unwrap();
+    pattern
+        .captures(module_docs)
+        .map(|caps| caps["title"].to_string())
+}
+
+/// The byte offset just past the end of the item starting at `start`, i.e. its closing brace, or the `;` of an item
+/// without a body. Braces in strings, chars, and comments are ignored.
+fn item_end(source: &str, start: usize) -> usize {
+    let bytes = source.as_bytes();
+    let mut depth = 0;
+    // Parentheses and brackets, so that e.g. the `;` in `[u8; 4]` isn't taken as the end of the item
+    let mut nesting = 0;
+    let mut i = start;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = source[i..].find('\n').map_or(bytes.len(), |end| i + end);
+            }
+            b'r' if matches!(bytes.get(i + 1), Some(b'"' | b'#')) && !is_ident(bytes, i) => {
+                let hashes = source[i + 1..].bytes().take_while(|&b| b == b'#').count();
+                let close = format!("\"{}", "#".repeat(hashes));
+                let body = i + 2 + hashes;
+                i = source[body..]
+                    .find(&close)
+                    .map_or(bytes.len(), |end| body + end + close.len() - 1);
+            }
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+            }
+            // A char literal rather than a lifetime, e.g. '{' or '\''
+            b'\'' if bytes.get(i + 2) == Some(&b'\'') => i += 2,
+            b'\'' if bytes.get(i + 1) == Some(&b'\\') => {
+                i = source[i + 3..]
+                    .find('\'')
+                    .map_or(bytes.len(), |end| i + 3 + end);
+            }
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            b'(' | b'[' => nesting += 1,
+            b')' | b']' => nesting -= 1,
+            b';' if depth == 0 && nesting == 0 => return i + 1,
+            _ => (),
+        }
+        i += 1;
+    }
+
+    bytes.len()
+}
+
+fn is_ident(bytes: &[u8], i: usize) -> bool {
+    i > 0 && (bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_')
+}
+
+/// Remove `indent` leading characters from each line, where they are whitespace
+fn dedent(text: &str, indent: usize) -> String {
+    text.lines()
+        .map(|line| {
+            let whitespace = line.len() - line.trim_start().len();
+            &line[whitespace.min(indent)..]
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Find the functions with `///` docs, including methods, in the order they appear. Stops at the tests module.
+fn documented_functions(source: &str) -> Vec<DocumentedFunction> {
+    let fn_pattern = Regex::new(r"^\s*(pub(\([^)]*\))?\s+)?(const\s+)?fn\s+(?<name>\w+)").unwrap();
+    let mut functions = Vec::new();
+    let mut docs: Vec<&str> = Vec::new();
+    let mut offset = 0;
+
+    while offset < source.len() {
+        let line_end = source[offset..]
+            .find('\n')
+            .map_or(source.len(), |end| offset + end + 1);
+        let line = &source[offset..line_end];
+        let trimmed = line.trim();
+
+        if trimmed == "#[cfg(test)]" {
+            break;
+        } else if trimmed.starts_with("///") {
+            docs.push(doc_line(line, "///"));
+        } else if trimmed.starts_with("#[") || trimmed.starts_with("//") {
+            // Attributes and plain comments between the docs and the item
+        } else if let Some(caps) = fn_pattern.captures(line) {
+            let end = item_end(source, offset);
+            if !docs.is_empty() {
+                let indent = line.len() - line.trim_start().len();
+                functions.push(DocumentedFunction {
+                    name: caps["name"].to_string(),
+                    docs: docs.join("\n").trim().to_string(),
+                    source: dedent(&source[offset..end], indent),
+                });
+            }
+            docs.clear();
+            offset = end;
+            continue;
+        } else {
+            docs.clear();
+        }
+
+        offset = line_end;
+    }
+
+    functions
+}
+
+/// Turn rustdoc links into inline code, e.g. ``[`Foo::bar`]`` becomes `Foo::bar`. Links with a URL are kept.
+fn strip_doc_links(docs: &str) -> String {
+    let pattern = Regex::new(r"\[(?<code>`[^`\]]+`)\]([^(\[]|$)").unwrap();
+    pattern.replace_all(docs, "$code$2").to_string()
+}
+
+/// Split a post into its front matter, including the `---` lines, and the rest
+fn split_front_matter(post: &str) -> Option<(&str, &str)> {
+    let rest = post.strip_prefix("---\n")?;
+    let end = rest
+        .find("\n---\n")
+        .map(|end| end + 5)
+        .or_else(|| rest.strip_suffix("\n---").map(|_| rest.len()))?;
+    let split = end + 4;
+
+    Some((&post[..split], &post[split..]))
+}
+
+/// The front matter for a new post, in the same format as `templates/blog_post.md.tpl`
+fn front_matter(day: u8, title: &str) -> String {
+    format!("---\nday: {day}\ntags: [post]\nheader: 'Day {day}: {title}'\n---\n")
+}
+
+/// The body of a draft post, without the front matter or marker
+fn render_body(source: &str) -> String {
+    let mut body = strip_doc_links(&module_docs(source))
+        .lines()
+        .skip_while(|line| line.starts_with("This is my solution for"))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+
+    for function in documented_functions(source) {
+        body.push_str(&format!(
+            "\n\n## `{}`\n\n{}\n\n```rust\n{}\n```",
+            function.name,
+            strip_doc_links(&function.docs),
+            function.source
+        ));
+    }
+
+    body.trim_start().to_string() + "\n"
+}
+
+/// SHA-256 of a draft's text as a hex string, so that it is the same between builds
+fn checksum(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text.as_bytes()))
+}
+
+fn marker(day: u8, body: &str) -> String {
+    format!(
+        "{MARKER_PREFIX}src/day_{day}.rs, checksum {} -->",
+        checksum(body)
+    )
+}
+
+/// Can `body` (the post without its front matter) be replaced by a new draft?
+fn is_replaceable(body: &str) -> bool {
+    if body.trim().is_empty() {
+        return true;
+    }
+
+    let body = body.trim_start_matches('\n');
+    match body.split_once('\n') {
+        Some((marker_line, rest)) if marker_line.starts_with(MARKER_PREFIX) => marker_line
+            .ends_with(&format!(
+                "checksum {} -->",
+                checksum(rest.trim_start_matches('\n'))
+            )),
+        _ => false,
+    }
+}
+
+/// Draft the post for `day` from `root/src/day_N.rs` into `root/pubs/blog/day_N.md`, keeping the existing front matter
+pub fn draft_post(root: &Path, day: u8) -> io::Result<DraftOutcome> {
+    let source = fs::read_to_string(root.join(format!("src/day_{day}.rs")))?;
+    let path = root.join(format!("pubs/blog/day_{day}.md"));
+
+    let existing = match fs::read_to_string(&path) {
+        Ok(post) => Some(post),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e),
+    };
+
+    let front_matter = match existing.as_deref().map(split_front_matter) {
+        Some(Some((front_matter, body))) if is_replaceable(body) => front_matter.to_string(),
+        Some(_) => return Ok(DraftOutcome::EditedByHand(path)),
+        None => {
+            let title = title(&module_docs(&source)).unwrap_or("???".to_string());
+            front_matter(day, &title)
+        }
+    };
+
+    let body = render_body(&source);
+    fs::write(
+        &path,
+        format!("{front_matter}\n{}\n\n{body}", marker(day, &body)),
+    )?;
+
+    Ok(DraftOutcome::Written(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blog_draft::*;
+    use std::env::temp_dir;
+
+    const SOURCE: &str = r#"//! This is my solution for [Advent of Code - Day 24: _Crossed Wires_](https://adventofcode.com/2024/day/24)
+//!
+//! [`parse_input`] reads the wires, and [`Circuit::run`] solves part 1.
+
+use crate::runner::Runner;
+
+/// The entry point
+pub fn run(runner: &mut Runner) {
+    let _contents = runner.input();
+}
+
+/// Parse the wires, e.g.
+///
+/// ```text
+/// x00: 1
+/// ```
+fn parse_input(input: &String) -> Vec<char> {
+    let brace = '{';
+    let text = "}";
+    let pattern = r"\d{1,3}";
+    input.chars().filter(|&c| c != brace).collect()
+}
+
+fn undocumented() {}
+
+struct Circuit;
+
+impl Circuit {
+    /// Run the circuit, see [`parse_input`]
+    #[allow(dead_code)]
+    fn run<'a>(&'a self, _inputs: [u8; 4]) -> &'a str {
+        if true {
+            "done"
+        } else {
+            "}"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    /// Not included
+    fn example() {}
+}
+"#;
+
+    #[test]
+    fn can_extract_module_docs() {
+        assert_eq!(
+            module_docs(SOURCE),
+            "This is my solution for [Advent of Code - Day 24: _Crossed Wires_](https://adventofcode.com/2024/day/24)
+
+[`parse_input`] reads the wires, and [`Circuit::run`] solves part 1."
+        );
+        assert_eq!(
+            title(&module_docs(SOURCE)),
+            Some("Crossed Wires".to_string())
+        );
+    }
+
+    #[test]
+    fn can_extract_documented_functions() {
+        let functions = documented_functions(SOURCE);
+        assert_eq!(
+            functions
+                .iter()
+                .map(|f| f.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["run", "parse_input", "run"]
+        );
+
+        assert_eq!(
+            functions[1].docs,
+            "Parse the wires, e.g.\n\n```text\nx00: 1\n```"
+        );
+        assert_eq!(
+            functions[1].source,
+            r#"fn parse_input(input: &String) -> Vec<char> {
+    let brace = '{';
+    let text = "}";
+    let pattern = r"\d{1,3}";
+    input.chars().filter(|&c| c != brace).collect()
+}"#
+        );
+        assert_eq!(
+            functions[2].source,
+            r#"fn run<'a>(&'a self, _inputs: [u8; 4]) -> &'a str {
+    if true {
+        "done"
+    } else {
+        "}"
+    }
+}"#
+        );
+    }
+
+    #[test]
+    fn can_extract_functions_from_days() {
+        let source = fs::read_to_string("src/day_22.rs").unwrap();
+        let functions = documented_functions(&source);
+        let shift = functions
+            .iter()
+            .find(|f| f.name == "shift_diff_into_sequence_id")
+            .unwrap();
+
+        assert!(shift.docs.starts_with("For performance reasons"));
+        assert!(shift.source.starts_with("fn shift_diff_into_sequence_id("));
+        assert!(shift.source.ends_with('}'));
+        assert!(functions.iter().all(|f| f.name != "can_parse_input"));
+    }
+
+    #[test]
+    fn can_strip_doc_links() {
+        assert_eq!(
+            strip_doc_links(
+                "[`parse_input`] then [`Foo::bar`], [site](https://example.com) [`kept`](url)"
+            ),
+            "`parse_input` then `Foo::bar`, [site](https://example.com) [`kept`](url)"
+        );
+    }
+
+    #[test]
+    fn can_split_front_matter() {
+        assert_eq!(
+            split_front_matter("---\nday: 1\n---\n\nBody\n"),
+            Some(("---\nday: 1\n---\n", "\nBody\n"))
+        );
+        assert_eq!(
+            split_front_matter("---\nday: 1\n---"),
+            Some(("---\nday: 1\n---", ""))
+        );
+        assert_eq!(split_front_matter("No front matter"), None);
+    }
+
+    fn setup(name: &str) -> PathBuf {
+        let root = temp_dir().join(format!("aoc-draft-{name}-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("pubs/blog")).unwrap();
+        fs::write(root.join("src/day_24.rs"), SOURCE).unwrap();
+
+        root
+    }
+
+    #[test]
+    fn can_draft_new_post() {
+        let root = setup("new");
+
+        let path = root.join("pubs/blog/day_24.md");
+        assert_eq!(
+            draft_post(&root, 24).unwrap(),
+            DraftOutcome::Written(path.clone())
+        );
+
+        let post = fs::read_to_string(&path).unwrap();
+        assert!(post.starts_with(
+            "---\nday: 24\ntags: [post]\nheader: 'Day 24: Crossed Wires'\n---\n\n<!-- Draft generated from src/day_24.rs"
+        ));
+        assert!(post.contains(
+            "\n\n`parse_input` reads the wires, and `Circuit::run` solves part 1.\n\n## `run`\n\n"
+        ));
+        assert!(post.contains("## `parse_input`\n\nParse the wires, e.g.\n\n```text\nx00: 1\n```\n\n```rust\nfn parse_input"));
+        assert!(!post.contains("This is my solution"));
+        assert!(!post.contains("Not included"));
+
+        // An untouched draft can be regenerated
+        assert_eq!(draft_post(&root, 24).unwrap(), DraftOutcome::Written(path));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn keeps_scaffolded_front_matter() {
+        let root = setup("scaffolded");
+        let path = root.join("pubs/blog/day_24.md");
+        fs::write(
+            &path,
+            "---\nday: 24\ntags: [post]\nheader: 'Day 24: Custom'\n---\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            draft_post(&root, 24).unwrap(),
+            DraftOutcome::Written(path.clone())
+        );
+        assert!(fs::read_to_string(&path).unwrap().starts_with(
+            "---\nday: 24\ntags: [post]\nheader: 'Day 24: Custom'\n---\n\n<!-- Draft"
+        ));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn does_not_overwrite_edited_posts() {
+        let root = setup("edited");
+        let path = root.join("pubs/blog/day_24.md");
+
+        draft_post(&root, 24).unwrap();
+        let edited = fs::read_to_string(&path).unwrap() + "\nSome thoughts of my own.\n";
+        fs::write(&path, &edited).unwrap();
+        assert_eq!(
+            draft_post(&root, 24).unwrap(),
+            DraftOutcome::EditedByHand(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), edited);
+
+        let hand_written = "---\nday: 24\n---\n\nWritten by hand\n";
+        fs::write(&path, hand_written).unwrap();
+        assert_eq!(
+            draft_post(&root, 24).unwrap(),
+            DraftOutcome::EditedByHand(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), hand_written);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
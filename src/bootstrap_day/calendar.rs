@@ -0,0 +1,80 @@
+//! Reading the stars earned for each day from the event's calendar page.
+//!
+//! Each unlocked day is a link with a `calendar-dayN` class, which also has `calendar-complete` once the first star
+//! has been earned, or `calendar-verycomplete` once both have. Days that haven't unlocked yet aren't links.
+
+use scraper::{Html, Selector};
+use std::collections::BTreeMap;
+
+/// The stars earned for each unlocked day on the calendar page
+pub fn parse_calendar(html: &str) -> BTreeMap<u8, u8> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("a").unwrap();
+
+    document
+        .select(&selector)
+        .filter_map(|link| {
+            let classes: Vec<&str> = link.value().classes().collect();
+            let day = classes
+                .iter()
+                .find_map(|class| class.strip_prefix("calendar-day")?.parse().ok())?;
+            let stars = if classes.contains(&"calendar-verycomplete") {
+                2
+            } else if classes.contains(&"calendar-complete") {
+                1
+            } else {
+                0
+            };
+
+            Some((day, stars))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bootstrap_day::calendar::*;
+
+    /// Saved from the calendar page part way through the event, trimmed to the first few days and the locked days
+    const CALENDAR: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<head>
+<meta charset="utf-8"/>
+<title>Advent of Code 2024</title>
+</head><!--
+
+
+
+
+Oh, hello!  Funny seeing you here.
+
+-->
+<body>
+<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1><div class="user">someone <span class="star-count">5*</span></div></div></header>
+<main>
+<pre class="calendar">
+<a aria-label="Day 1, two stars" href="/2024/day/1" class="calendar-day1 calendar-verycomplete"><span class="calendar-color-w">.-----.</span>          <span class="calendar-day"> 1</span> <span class="calendar-mark-complete">*</span><span class="calendar-mark-verycomplete">*</span></a>
+<a aria-label="Day 2, one star" href="/2024/day/2" class="calendar-day2 calendar-complete"><span class="calendar-color-w">|     |</span>          <span class="calendar-day"> 2</span> <span class="calendar-mark-complete">*</span><span class="calendar-mark-verycomplete">*</span></a>
+<a aria-label="Day 3" href="/2024/day/3" class="calendar-day3"><span class="calendar-color-w">|  .  |</span>          <span class="calendar-day"> 3</span> <span class="calendar-mark-complete">*</span><span class="calendar-mark-verycomplete">*</span></a>
+<a aria-label="Day 10, two stars" href="/2024/day/10" class="calendar-day10 calendar-verycomplete"><span class="calendar-color-w">'-----'</span>          <span class="calendar-day">10</span> <span class="calendar-mark-complete">*</span><span class="calendar-mark-verycomplete">*</span></a>
+<span aria-hidden="true" class="calendar-day11">                                     <span class="calendar-day">11</span></span>
+<span aria-hidden="true" class="calendar-day12">                                     <span class="calendar-day">12</span></span>
+</pre>
+</main>
+</body>
+</html>
+"#;
+
+    #[test]
+    fn can_parse_calendar() {
+        assert_eq!(
+            parse_calendar(CALENDAR),
+            BTreeMap::from([(1, 2), (2, 1), (3, 0), (10, 2)])
+        );
+    }
+
+    #[test]
+    fn can_parse_calendar_without_days() {
+        assert!(parse_calendar("<html><body><main>Not logged in</main></body></html>").is_empty());
+    }
+}
@@ -0,0 +1,428 @@
+//! The operations the bootstrapper needs from the Advent of Code site, behind the [`PuzzleClient`] trait so that the
+//! scaffolding logic can be tested without the live site.
+//!
+//! [`HttpPuzzleClient`] talks to the site using the stored session cookie, and `FakePuzzleClient` serves canned
+//! files from a directory for tests.
+
+use super::fetch::{classify_response, fetch, fetch_with_retry, RETRY_BACKOFF};
+use super::Result;
+use crate::YEAR;
+use reqwest::blocking::Client;
+use reqwest::cookie::Jar;
+use reqwest::Url;
+use scraper::{Html, Selector};
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The live site
+pub const BASE_URL: &str = "https://adventofcode.com";
+/// Where the session cookie is stored, this is not committed
+pub const SESSION_COOKIE_PATH: &str = "res/session_cookie.txt";
+
+/// What the site said about a submitted answer
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum SubmissionOutcome {
+    Correct,
+    /// The answer was wrong, with the site's hint if it gave one, e.g. "your answer is too high"
+    Incorrect(Option<String>),
+    /// An answer was submitted too recently, the message includes how long to wait
+    TooSoon(String),
+    /// The part has already been completed, or part 1 hasn't been completed yet
+    WrongLevel,
+    /// Any other response, with the message from the page
+    Unknown(String),
+}
+
+impl SubmissionOutcome {
+    /// Interpret the page returned after submitting an answer
+    pub fn from_html(html: &str) -> SubmissionOutcome {
+        let document = Html::parse_document(html);
+        let selector = Selector::parse("main > article").unwrap();
+        let message = document
+            .select(&selector)
+            .next()
+            .map(|article| article.text().collect::<String>())
+            .unwrap_or_else(|| html.to_string());
+        let message = message.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        if message.contains("That's the right answer") {
+            SubmissionOutcome::Correct
+        } else if message.contains("That's not the right answer") {
+            let hint = ["your answer is too high", "your answer is too low"]
+                .into_iter()
+                .find(|hint| message.contains(hint))
+                .map(|hint| hint.to_string());
+            SubmissionOutcome::Incorrect(hint)
+        } else if message.contains("You gave an answer too recently") {
+            SubmissionOutcome::TooSoon(message)
+        } else if message.contains("You don't seem to be solving the right level") {
+            SubmissionOutcome::WrongLevel
+        } else {
+            SubmissionOutcome::Unknown(message)
+        }
+    }
+}
+
+/// The requests made to the Advent of Code site
+pub trait PuzzleClient {
+    /// The puzzle input for `day`
+    fn fetch_input(&self, day: u8) -> Result<String>;
+    /// The HTML of the puzzle description page for `day`
+    fn fetch_puzzle_page(&self, day: u8) -> Result<String>;
+    /// The HTML of the event's calendar page, which shows the stars earned for each day
+    fn fetch_calendar_page(&self) -> Result<String>;
+    /// The HTML of the personal leaderboard page, which has the completion times and ranks for each day
+    fn fetch_personal_stats_page(&self) -> Result<String>;
+    /// Submit an answer for one part of a day's puzzle
+    fn submit_answer(&self, day: u8, part: u8, answer: &str) -> Result<SubmissionOutcome>;
+}
+
+/// A [`PuzzleClient`] that makes requests to the site, authenticated by a session cookie
+pub struct HttpPuzzleClient {
+    client: Client,
+    base_url: String,
+    /// The delays between retrying a day's input or puzzle page while it is locked, none unless waiting for it
+    backoff: Vec<Duration>,
+}
+
+impl HttpPuzzleClient {
+    /// A client for `base_url` that sends `session` as its session cookie
+    pub fn new(base_url: &str, session: &str) -> Result<HttpPuzzleClient> {
+        let url = base_url.parse::<Url>().unwrap();
+        let jar = Jar::default();
+        jar.add_cookie_str(&format!("session={}", session.trim()), &url);
+
+        let client = Client::builder()
+            .cookie_store(true)
+            .cookie_provider(Arc::new(jar))
+            .build()?;
+
+        Ok(HttpPuzzleClient {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            backoff: Vec::new(),
+        })
+    }
+
+    /// Retry fetching a day's input and puzzle page with [`RETRY_BACKOFF`] while the day is still locked, for when
+    /// waiting for it to unlock
+    pub fn retrying_until_unlocked(self) -> HttpPuzzleClient {
+        HttpPuzzleClient {
+            backoff: RETRY_BACKOFF.to_vec(),
+            ..self
+        }
+    }
+
+    /// A client for the live site, using the session cookie stored in `res/session_cookie.txt`
+    pub fn from_session_file() -> Result<HttpPuzzleClient> {
+        let session = fs::read_to_string(SESSION_COOKIE_PATH)?;
+        HttpPuzzleClient::new(BASE_URL, &session)
+    }
+
+    fn day_url(&self, day: u8) -> String {
+        format!("{}/{YEAR}/day/{day}", self.base_url)
+    }
+}
+
+impl PuzzleClient for HttpPuzzleClient {
+    fn fetch_input(&self, day: u8) -> Result<String> {
+        let url = format!("{}/input", self.day_url(day));
+        fetch_with_retry(&self.client, &url, &self.backoff)
+    }
+
+    fn fetch_puzzle_page(&self, day: u8) -> Result<String> {
+        fetch_with_retry(&self.client, &self.day_url(day), &self.backoff)
+    }
+
+    fn fetch_calendar_page(&self) -> Result<String> {
+        let url = format!("{}/{YEAR}", self.base_url);
+        fetch(&self.client, &url)
+    }
+
+    fn fetch_personal_stats_page(&self) -> Result<String> {
+        let url = format!("{}/{YEAR}/leaderboard/self", self.base_url);
+        fetch(&self.client, &url)
+    }
+
+    fn submit_answer(&self, day: u8, part: u8, answer: &str) -> Result<SubmissionOutcome> {
+        let url = format!("{}/answer", self.day_url(day));
+        let response = self
+            .client
+            .post(&url)
+            .form(&[("level", part.to_string()), ("answer", answer.to_string())])
+            .send()?;
+        let status = response.status();
+        let body = classify_response(&url, status, response.text()?)?;
+
+        Ok(SubmissionOutcome::from_html(&body))
+    }
+}
+
+#[cfg(test)]
+pub use fake::FakePuzzleClient;
+
+#[cfg(test)]
+mod fake {
+    use super::{PuzzleClient, SubmissionOutcome};
+    use crate::bootstrap_day::{ErrorKind, Result};
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// A [`PuzzleClient`] that serves canned files from a directory:
+    ///
+    /// - `day-N-input.txt` for the input
+    /// - `day-N-puzzle.html` for the puzzle page
+    /// - `calendar.html` for the calendar page
+    /// - `personal-stats.html` for the personal leaderboard page
+    /// - `day-N-part-P-answer.txt` the correct answer that submissions are checked against
+    ///
+    /// Missing files are reported as [`ErrorKind::NotFound`], as the site would.
+    pub struct FakePuzzleClient {
+        dir: PathBuf,
+    }
+
+    impl FakePuzzleClient {
+        pub fn new(dir: impl Into<PathBuf>) -> FakePuzzleClient {
+            FakePuzzleClient { dir: dir.into() }
+        }
+
+        fn read(&self, file: String) -> Result<String> {
+            let path = self.dir.join(file);
+            fs::read_to_string(&path)
+                .map_err(|_| ErrorKind::NotFound(path.to_string_lossy().to_string()).into())
+        }
+    }
+
+    impl PuzzleClient for FakePuzzleClient {
+        fn fetch_input(&self, day: u8) -> Result<String> {
+            self.read(format!("day-{day}-input.txt"))
+        }
+
+        fn fetch_puzzle_page(&self, day: u8) -> Result<String> {
+            self.read(format!("day-{day}-puzzle.html"))
+        }
+
+        fn fetch_calendar_page(&self) -> Result<String> {
+            self.read("calendar.html".to_string())
+        }
+
+        fn fetch_personal_stats_page(&self) -> Result<String> {
+            self.read("personal-stats.html".to_string())
+        }
+
+        fn submit_answer(&self, day: u8, part: u8, answer: &str) -> Result<SubmissionOutcome> {
+            let expected = self.read(format!("day-{day}-part-{part}-answer.txt"))?;
+            if expected.trim() == answer {
+                Ok(SubmissionOutcome::Correct)
+            } else {
+                Ok(SubmissionOutcome::Incorrect(None))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bootstrap_day::client::*;
+    use crate::bootstrap_day::ErrorKind;
+    use std::thread;
+    use tiny_http::{Method, Response, Server};
+
+    fn article(message: &str) -> String {
+        format!("<html><body><main><article><p>{message}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn can_interpret_submission_responses() {
+        assert_eq!(
+            SubmissionOutcome::from_html(&article(
+                "That's the right answer! You are one gold star closer to finding the Chief Historian."
+            )),
+            SubmissionOutcome::Correct
+        );
+        assert_eq!(
+            SubmissionOutcome::from_html(&article(
+                "That's not the right answer; your answer is too high. Please wait one minute before trying again."
+            )),
+            SubmissionOutcome::Incorrect(Some("your answer is too high".to_string()))
+        );
+        assert_eq!(
+            SubmissionOutcome::from_html(&article("That's not the right answer.")),
+            SubmissionOutcome::Incorrect(None)
+        );
+        assert_eq!(
+            SubmissionOutcome::from_html(&article(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again. \
+                You have 34s left to wait."
+            )),
+            SubmissionOutcome::TooSoon(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying \
+                again. You have 34s left to wait."
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            SubmissionOutcome::from_html(&article(
+                "You don't seem to be solving the right level. Did you already complete it?"
+            )),
+            SubmissionOutcome::WrongLevel
+        );
+        assert_eq!(
+            SubmissionOutcome::from_html(&article("Something else")),
+            SubmissionOutcome::Unknown("Something else".to_string())
+        );
+    }
+
+    #[test]
+    fn can_make_requests_to_site() {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr());
+
+        let handle = thread::spawn(move || {
+            let mut seen = Vec::new();
+            for _ in 0..5 {
+                let mut request = server.recv().unwrap();
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let cookie = request
+                    .headers()
+                    .iter()
+                    .find(|header| header.field.equiv("Cookie"))
+                    .map(|header| header.value.to_string());
+                seen.push((
+                    request.method().clone(),
+                    request.url().to_string(),
+                    body,
+                    cookie,
+                ));
+
+                let response = match request.url() {
+                    "/2024/day/1/input" => "3   4\n".to_string(),
+                    "/2024/day/1/answer" => article("That's the right answer!"),
+                    "/2024/leaderboard/self" => "<pre>  1   00:05:12    847      0</pre>".to_string(),
+                    "/2024" => "<a class=\"calendar-day1 calendar-complete\">1</a>".to_string(),
+                    _ => "<article class=\"day-desc\"><h2>--- Day 1: Historian Hysteria ---</h2></article>"
+                        .to_string(),
+                };
+                request.respond(Response::from_string(response)).unwrap();
+            }
+            seen
+        });
+
+        let client = HttpPuzzleClient::new(&base_url, "abc123\n").unwrap();
+        assert_eq!(client.fetch_input(1).unwrap(), "3   4\n");
+        assert!(client
+            .fetch_puzzle_page(1)
+            .unwrap()
+            .contains("Historian Hysteria"));
+        assert_eq!(
+            client.submit_answer(1, 2, "31").unwrap(),
+            SubmissionOutcome::Correct
+        );
+        assert!(client
+            .fetch_calendar_page()
+            .unwrap()
+            .contains("calendar-complete"));
+        assert!(client
+            .fetch_personal_stats_page()
+            .unwrap()
+            .contains("00:05:12"));
+
+        let session = Some("session=abc123".to_string());
+        assert_eq!(
+            handle.join().unwrap(),
+            vec![
+                (
+                    Method::Get,
+                    "/2024/day/1/input".to_string(),
+                    "".to_string(),
+                    session.clone()
+                ),
+                (
+                    Method::Get,
+                    "/2024/day/1".to_string(),
+                    "".to_string(),
+                    session.clone()
+                ),
+                (
+                    Method::Post,
+                    "/2024/day/1/answer".to_string(),
+                    "level=2&answer=31".to_string(),
+                    session.clone()
+                ),
+                (
+                    Method::Get,
+                    "/2024".to_string(),
+                    "".to_string(),
+                    session.clone()
+                ),
+                (
+                    Method::Get,
+                    "/2024/leaderboard/self".to_string(),
+                    "".to_string(),
+                    session
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn does_not_retry_unless_waiting_for_unlock() {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr());
+
+        let handle = thread::spawn(move || {
+            let mut urls = Vec::new();
+            for _ in 0..2 {
+                let request = server.recv().unwrap();
+                urls.push(request.url().to_string());
+                request
+                    .respond(Response::from_string("404 Not Found").with_status_code(404))
+                    .unwrap();
+            }
+            urls
+        });
+
+        let client = HttpPuzzleClient::new(&base_url, "abc123").unwrap();
+        assert!(matches!(
+            client.fetch_input(26).unwrap_err().kind(),
+            ErrorKind::NotFound(_)
+        ));
+
+        // Only a day's input and puzzle page are worth waiting for
+        let client = client.retrying_until_unlocked();
+        assert!(matches!(
+            client.fetch_calendar_page().unwrap_err().kind(),
+            ErrorKind::NotFound(_)
+        ));
+
+        assert_eq!(
+            handle.join().unwrap(),
+            vec!["/2024/day/26/input".to_string(), "/2024".to_string()]
+        );
+    }
+
+    #[test]
+    fn can_serve_canned_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-fake-client-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day-1-input.txt"), "3   4\n").unwrap();
+        fs::write(dir.join("day-1-part-1-answer.txt"), "11\n").unwrap();
+
+        let client = FakePuzzleClient::new(&dir);
+        assert_eq!(client.fetch_input(1).unwrap(), "3   4\n");
+        assert!(client.fetch_puzzle_page(1).is_err());
+        assert!(client.fetch_input(2).is_err());
+        assert_eq!(
+            client.submit_answer(1, 1, "11").unwrap(),
+            SubmissionOutcome::Correct
+        );
+        assert_eq!(
+            client.submit_answer(1, 1, "12").unwrap(),
+            SubmissionOutcome::Incorrect(None)
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
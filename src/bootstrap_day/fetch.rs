@@ -0,0 +1,254 @@
+//! Fetching pages from the Advent of Code site, taking care not to treat error pages as puzzle input.
+//!
+//! Requesting a day's input before it has unlocked returns a page asking you not to do that, and requesting it
+//! without a valid session returns a page asking you to log in. [`fetch`] turns those, and 404s, into errors.
+//! [`wait_for_unlock`] shows a countdown until the puzzle unlocks at midnight EST, and [`fetch_with_retry`] retries
+//! while the day is still locked, in case the clocks are slightly out.
+
+use super::{Error, ErrorKind, Result};
+use crate::YEAR;
+use reqwest::blocking::Client;
+use reqwest::StatusCode;
+use std::io::{self, Write};
+use std::thread::sleep;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The delays between attempts when a page is not available yet
+pub const RETRY_BACKOFF: [Duration; 5] = [
+    Duration::from_secs(2),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+    Duration::from_secs(30),
+    Duration::from_secs(60),
+];
+
+/// Puzzles unlock at midnight EST, which is 05:00 UTC
+const UNLOCK_OFFSET: u64 = 5 * 60 * 60;
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Check a response for the ways the site reports the page isn't available, returning the body if it is OK.
+pub fn classify_response(url: &str, status: StatusCode, body: String) -> Result<String> {
+    if status == StatusCode::NOT_FOUND {
+        Err(ErrorKind::NotFound(url.to_string()).into())
+    } else if body.contains("before it unlocks") {
+        Err(ErrorKind::NotYetUnlocked(url.to_string()).into())
+    } else if body.contains("Please log in") {
+        Err(ErrorKind::NotLoggedIn(url.to_string()).into())
+    } else if !status.is_success() {
+        Err(ErrorKind::UnexpectedStatus(url.to_string(), status.as_u16()).into())
+    } else {
+        Ok(body)
+    }
+}
+
+/// GET `url` and check the response is the page requested
+pub fn fetch(client: &Client, url: &str) -> Result<String> {
+    let response = client.get(url).send()?;
+    let status = response.status();
+    classify_response(url, status, response.text()?)
+}
+
+/// Is this error one that might resolve itself if the request is retried once the puzzle unlocks?
+fn is_locked(error: &Error) -> bool {
+    matches!(
+        error.kind(),
+        ErrorKind::NotYetUnlocked(_) | ErrorKind::NotFound(_)
+    )
+}
+
+/// [`fetch`], retrying after each of the `backoff` delays in turn if the page isn't unlocked yet
+pub fn fetch_with_retry(client: &Client, url: &str, backoff: &[Duration]) -> Result<String> {
+    let mut delays = backoff.iter();
+    loop {
+        match fetch(client, url) {
+            Err(error) if is_locked(&error) => match delays.next() {
+                Some(&delay) => {
+                    println!("{error}, retrying in {delay:?}");
+                    sleep(delay);
+                }
+                None => return Err(error),
+            },
+            result => return result,
+        }
+    }
+}
+
+/// The number of days from the unix epoch to 1st December of `year`. This uses the
+/// [days from civil](https://howardhinnant.github.io/date_algorithms.html#days_from_civil) algorithm, simplified as
+/// the month is always December, which is in the same "March-based" year as January 1st.
+fn days_to_december_first(year: u16) -> u64 {
+    let year = year as u64;
+    let era = year / 400;
+    let year_of_era = year % 400;
+    // Days from 1st March to 1st December
+    let day_of_year = 275;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// When the puzzle for `day` unlocks
+pub fn unlock_time(day: u8) -> SystemTime {
+    let seconds = (days_to_december_first(YEAR) + day as u64 - 1) * SECONDS_PER_DAY + UNLOCK_OFFSET;
+    UNIX_EPOCH + Duration::from_secs(seconds)
+}
+
+/// Format a duration as `HH:MM:SS` for the countdown
+fn format_countdown(remaining: Duration) -> String {
+    let seconds = remaining.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds / 60) % 60,
+        seconds % 60
+    )
+}
+
+/// If `day` hasn't unlocked yet, show a countdown and block until it has
+pub fn wait_for_unlock(day: u8) {
+    let unlock = unlock_time(day);
+    while let Ok(remaining) = unlock.duration_since(SystemTime::now()) {
+        print!("\rDay {day} unlocks in {}", format_countdown(remaining));
+        io::stdout().flush().unwrap();
+        sleep(remaining.min(Duration::from_secs(1)));
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bootstrap_day::fetch::*;
+    use std::thread;
+    use tiny_http::{Header, Response, Server};
+
+    /// Serve each of `responses` in order to successive requests, returning the base URL of the stand-in server
+    fn serve(responses: Vec<(u16, &'static str)>) -> String {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr());
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let request = server.recv().unwrap();
+                let content_type = Header::from_bytes("Content-Type", "text/plain").unwrap();
+                let response = Response::from_string(body)
+                    .with_status_code(status)
+                    .with_header(content_type);
+                request.respond(response).unwrap();
+            }
+        });
+
+        url
+    }
+
+    const LOCKED: &str = "Please don't repeatedly request this endpoint before it unlocks! \
+        The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the \
+        instant this puzzle becomes available.";
+    const LOGGED_OUT: &str =
+        "Puzzle inputs differ by user.  Please log in to get your puzzle input.";
+
+    #[test]
+    fn can_classify_responses() {
+        let client = Client::new();
+        let url = serve(vec![
+            (200, "3   4\n4   3\n"),
+            (404, "404 Not Found"),
+            (200, LOCKED),
+            (400, LOGGED_OUT),
+            (500, "Internal Server Error"),
+        ]);
+
+        assert_eq!(fetch(&client, &url).unwrap(), "3   4\n4   3\n");
+        assert!(matches!(
+            fetch(&client, &url).unwrap_err().kind(),
+            ErrorKind::NotFound(_)
+        ));
+        assert!(matches!(
+            fetch(&client, &url).unwrap_err().kind(),
+            ErrorKind::NotYetUnlocked(_)
+        ));
+        assert!(matches!(
+            fetch(&client, &url).unwrap_err().kind(),
+            ErrorKind::NotLoggedIn(_)
+        ));
+        assert!(matches!(
+            fetch(&client, &url).unwrap_err().kind(),
+            ErrorKind::UnexpectedStatus(_, 500)
+        ));
+    }
+
+    #[test]
+    fn can_retry_until_unlocked() {
+        let client = Client::new();
+        let url = serve(vec![
+            (404, "404 Not Found"),
+            (200, LOCKED),
+            (200, "1 2 3\n"),
+        ]);
+
+        assert_eq!(
+            fetch_with_retry(&client, &url, &[Duration::ZERO, Duration::ZERO]).unwrap(),
+            "1 2 3\n"
+        );
+    }
+
+    #[test]
+    fn can_give_up_retrying() {
+        let client = Client::new();
+        let url = serve(vec![(200, LOCKED), (200, LOCKED)]);
+
+        assert!(matches!(
+            fetch_with_retry(&client, &url, &[Duration::ZERO])
+                .unwrap_err()
+                .kind(),
+            ErrorKind::NotYetUnlocked(_)
+        ));
+    }
+
+    #[test]
+    fn does_not_retry_when_logged_out() {
+        let client = Client::new();
+        let url = serve(vec![(400, LOGGED_OUT)]);
+
+        assert!(matches!(
+            fetch_with_retry(&client, &url, &[Duration::ZERO])
+                .unwrap_err()
+                .kind(),
+            ErrorKind::NotLoggedIn(_)
+        ));
+    }
+
+    #[test]
+    fn can_calculate_unlock_time() {
+        assert_eq!(
+            unlock_time(1).duration_since(UNIX_EPOCH).unwrap().as_secs(),
+            1_733_029_200 // 2024-12-01T05:00:00Z
+        );
+        assert_eq!(
+            unlock_time(25)
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            1_735_102_800 // 2024-12-25T05:00:00Z
+        );
+    }
+
+    #[test]
+    fn can_count_days_to_december() {
+        assert_eq!(days_to_december_first(1970), 334);
+        assert_eq!(days_to_december_first(2023), 19_692);
+        assert_eq!(
+            days_to_december_first(2024) * SECONDS_PER_DAY,
+            1_733_011_200
+        );
+    }
+
+    #[test]
+    fn can_format_countdown() {
+        assert_eq!(format_countdown(Duration::from_secs(0)), "00:00:00");
+        assert_eq!(format_countdown(Duration::from_millis(61_500)), "00:01:01");
+        assert_eq!(
+            format_countdown(Duration::from_secs(26 * 3600 + 5)),
+            "26:00:05"
+        );
+    }
+}
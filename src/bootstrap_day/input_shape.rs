@@ -0,0 +1,845 @@
+//! Guess the shape of a downloaded puzzle input, so that the scaffolded day starts with a typed `parse_input` and a
+//! test for it, rather than the same hand-written parsers each day.
+//!
+//! The input is split into blank-line separated sections, and each section is classified by its lines, trying the
+//! most specific shapes first. The generated parser is only a starting point, e.g. a grid is parsed as
+//! `Vec<Vec<char>>` rather than a struct for the day's map.
+
+use itertools::Itertools;
+use regex::Regex;
+
+/// How many lines of each section, or blocks of a [`InputShape::NumberBlocks`], to use as the example in the stub
+const EXCERPT_RECORDS: usize = 3;
+/// How many digits of a [`InputShape::DigitString`] to use as the example in the stub
+const EXCERPT_DIGITS: usize = 20;
+/// A line of only digits longer than this is treated as a sequence of single digits rather than one number
+const MAX_NUMBER_DIGITS: usize = 10;
+
+/// The layouts of puzzle input that have a matching parser
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum InputShape {
+    /// Lines of equal length with no whitespace, e.g. a map. `digits` is set when every cell is a digit.
+    Grid { digits: bool },
+    /// A single long line of digits, each a separate value
+    DigitString,
+    /// Lines of integers, possibly among other text with the same layout on each line, e.g. `p=0,4 v=3,-3`
+    NumberLines { one_per_line: bool },
+    /// Two values per line either side of a punctuation separator, e.g. `kh-tc` or `47|53`
+    Pairs { separator: char, numeric: bool },
+    /// Blank-line separated blocks with the same layout, parsed as the list of numbers in each block
+    NumberBlocks,
+    /// Blank-line separated sections with their own shapes
+    Sections(Vec<InputShape>),
+    /// Anything else, kept as lines of text
+    Lines,
+}
+
+/// The generated `parse_input` function and its test, ready to be substituted into the day template
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+pub struct ParseStub {
+    pub function: String,
+    pub test: String,
+}
+
+fn number_pattern() -> Regex {
+    Regex::new(r"-?\d+").unwrap()
+}
+
+/// The text with each number replaced by `#`, used to check if lines share a layout
+fn layout(text: &str, numbers: &Regex) -> String {
+    numbers.replace_all(text, "#").to_string()
+}
+
+fn sections(input: &str) -> Vec<&str> {
+    input.trim_end().split("\n\n").collect()
+}
+
+fn classify_pairs(lines: &[&str]) -> Option<InputShape> {
+    let pattern = Regex::new(r"^([[:alnum:]]+)([[:punct:]])([[:alnum:]]+)$").unwrap();
+    let captures: Vec<_> = lines
+        .iter()
+        .map(|line| pattern.captures(line))
+        .collect::<Option<_>>()?;
+
+    let separator = captures.first()?[2].chars().next()?;
+    if captures.iter().any(|caps| caps[2] != separator.to_string()) {
+        return None;
+    }
+
+    let numeric = captures
+        .iter()
+        .all(|caps| caps[1].parse::<i64>().is_ok() && caps[3].parse::<i64>().is_ok());
+
+    Some(InputShape::Pairs { separator, numeric })
+}
+
+/// Classify a section of input that has no blank lines
+fn classify_lines(lines: &[&str]) -> InputShape {
+    let numbers = number_pattern();
+    let all_have_numbers = lines.iter().all(|line| numbers.is_match(line));
+    let number_lines = || InputShape::NumberLines {
+        one_per_line: lines
+            .iter()
+            .all(|line| numbers.find_iter(line).count() == 1),
+    };
+
+    if let [line] = lines {
+        if line.len() > MAX_NUMBER_DIGITS && line.chars().all(|c| c.is_ascii_digit()) {
+            return InputShape::DigitString;
+        }
+    }
+
+    if let Some(pairs) = classify_pairs(lines) {
+        return pairs;
+    }
+
+    // Checked before grids so that e.g. `029A` on each line isn't treated as a map
+    let layouts = lines
+        .iter()
+        .map(|line| layout(line, &numbers))
+        .unique()
+        .collect_vec();
+    if lines.len() > 1 && all_have_numbers && layouts.len() == 1 && layouts[0] != "#" {
+        // A number run together with letters is part of a code, and parsing just the number would lose the rest
+        let codes = Regex::new(r"[[:alpha:]]-?\d|\d[[:alpha:]]").unwrap();
+        if lines.iter().any(|line| codes.is_match(line)) {
+            return InputShape::Lines;
+        }
+
+        return number_lines();
+    }
+
+    let is_grid = lines.len() > 1
+        && lines.iter().map(|line| line.len()).all_equal()
+        && !lines.iter().any(|line| line.contains(char::is_whitespace));
+    if is_grid {
+        return InputShape::Grid {
+            digits: lines
+                .iter()
+                .all(|line| line.chars().all(|c| c.is_ascii_digit())),
+        };
+    }
+
+    if all_have_numbers && !lines.iter().any(|line| line.contains(char::is_alphabetic)) {
+        return number_lines();
+    }
+
+    InputShape::Lines
+}
+
+/// Classify the shape of a puzzle input
+pub fn classify(input: &str) -> InputShape {
+    let sections = sections(input);
+
+    if sections.len() > 1 {
+        let numbers = number_pattern();
+        let layouts = sections
+            .iter()
+            .map(|section| layout(section, &numbers))
+            .unique()
+            .count();
+        if layouts == 1 && numbers.is_match(sections[0]) {
+            return InputShape::NumberBlocks;
+        }
+
+        return InputShape::Sections(
+            sections
+                .iter()
+                .map(|section| classify_lines(&section.lines().collect_vec()))
+                .collect(),
+        );
+    }
+
+    classify_lines(&sections[0].lines().collect_vec())
+}
+
+impl InputShape {
+    /// The type `parse_input` returns for this shape
+    fn rust_type(&self) -> String {
+        match self {
+            InputShape::Grid { digits: false } => "Vec<Vec<char>>".to_string(),
+            InputShape::Grid { digits: true } => "Vec<Vec<u8>>".to_string(),
+            InputShape::DigitString => "Vec<u8>".to_string(),
+            InputShape::NumberLines { one_per_line: true } => "Vec<i64>".to_string(),
+            InputShape::NumberLines {
+                one_per_line: false,
+            } => "Vec<Vec<i64>>".to_string(),
+            InputShape::Pairs { numeric: true, .. } => "Vec<(i64, i64)>".to_string(),
+            InputShape::Pairs { numeric: false, .. } => "Vec<(String, String)>".to_string(),
+            InputShape::NumberBlocks => "Vec<Vec<i64>>".to_string(),
+            InputShape::Sections(shapes) => {
+                format!(
+                    "({})",
+                    shapes.iter().map(|shape| shape.rust_type()).join(", ")
+                )
+            }
+            InputShape::Lines => "Vec<String>".to_string(),
+        }
+    }
+
+    /// An expression that parses the `&str` in the variable named `source` into [`Self::rust_type`]
+    fn parse_expression(&self, source: &str) -> String {
+        match self {
+            InputShape::Grid { digits: false } => {
+                format!("{source}.lines().map(|line| line.chars().collect()).collect()")
+            }
+            InputShape::Grid { digits: true } => format!(
+                "{source}.lines().map(|line| line.bytes().map(|b| b - b'0').collect()).collect()"
+            ),
+            InputShape::DigitString => {
+                format!("{source}.trim().bytes().map(|b| b - b'0').collect()")
+            }
+            InputShape::NumberLines { one_per_line: true } => {
+                format!("{source}.lines().flat_map(parse_numbers).collect()")
+            }
+            InputShape::NumberLines {
+                one_per_line: false,
+            } => {
+                format!("{source}.lines().map(parse_numbers).collect()")
+            }
+            InputShape::Pairs { separator, numeric } => {
+                let convert = if *numeric {
+                    "(a.parse().unwrap(), b.parse().unwrap())"
+                } else {
+                    "(a.to_string(), b.to_string())"
+                };
+                format!(
+                    "{source}.lines().flat_map(|line| line.split_once('{separator}')).map(|(a, b)| {convert}).collect()"
+                )
+            }
+            InputShape::NumberBlocks => {
+                format!("{source}.split(\"\\n\\n\").map(parse_numbers).collect()")
+            }
+            InputShape::Sections(_) => unreachable!("sections are parsed individually"),
+            InputShape::Lines => format!("{source}.lines().map(|line| line.to_string()).collect()"),
+        }
+    }
+
+    fn uses_parse_numbers(&self) -> bool {
+        match self {
+            InputShape::NumberLines { .. } | InputShape::NumberBlocks => true,
+            InputShape::Sections(shapes) => shapes.iter().any(|shape| shape.uses_parse_numbers()),
+            _ => false,
+        }
+    }
+}
+
+/// The start of the input to use as an example, and the number of records the stub should parse from each section
+fn excerpt(input: &str, shape: &InputShape) -> (String, Vec<usize>) {
+    match shape {
+        InputShape::DigitString => {
+            let digits: String = input.trim().chars().take(EXCERPT_DIGITS).collect();
+            let count = digits.len();
+            (digits, vec![count])
+        }
+        InputShape::NumberBlocks => {
+            let blocks = sections(input)
+                .into_iter()
+                .take(EXCERPT_RECORDS)
+                .collect_vec();
+            (blocks.join("\n\n"), vec![blocks.len()])
+        }
+        _ => {
+            let sections = sections(input)
+                .into_iter()
+                .map(|section| section.lines().take(EXCERPT_RECORDS).collect_vec())
+                .collect_vec();
+            let counts = sections.iter().map(|lines| lines.len()).collect();
+            (
+                sections.iter().map(|lines| lines.join("\n")).join("\n\n"),
+                counts,
+            )
+        }
+    }
+}
+
+fn string_literal(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn doc_example(text: &str) -> String {
+    text.lines()
+        .map(|line| format!("/// {line}").trim_end().to_string())
+        .join("\n")
+}
+
+/// Generate a `parse_input` function matching the shape of `input`, and a test that parses an excerpt of it
+pub fn parse_stub(input: &str) -> ParseStub {
+    let shape = classify(input);
+    let (example, counts) = excerpt(input, &shape);
+
+    let body = match &shape {
+        InputShape::Sections(shapes) => {
+            let names = (1..=shapes.len())
+                .map(|i| format!("section_{i}"))
+                .collect_vec();
+            let mut body = "    let mut sections = input.split(\"\\n\\n\");\n".to_string();
+            for name in &names {
+                body.push_str(&format!("    let {name} = sections.next().unwrap();\n"));
+            }
+            body.push_str("\n    (\n");
+            for (shape, name) in shapes.iter().zip(&names) {
+                body.push_str(&format!("        {},\n", shape.parse_expression(name)));
+            }
+            body.push_str("    )");
+            body
+        }
+        shape => format!("    {}", shape.parse_expression("input")),
+    };
+
+    let mut function = format!(
+        "/// Parse the puzzle input, e.g.
+///
+/// ```text
+{}
+/// ```
+fn parse_input(input: &String) -> {} {{
+{body}
+}}",
+        doc_example(&example),
+        shape.rust_type()
+    );
+
+    if shape.uses_parse_numbers() {
+        function.push_str(
+            "
+
+/// Extract the integers from some text, e.g. `p=0,4 v=3,-3` becomes `[0, 4, 3, -3]`
+fn parse_numbers(text: &str) -> Vec<i64> {
+    text.split(|c: char| !c.is_ascii_digit() && c != '-')
+        .filter_map(|number| number.parse().ok())
+        .collect()
+}",
+        );
+    }
+
+    let assertions = match &shape {
+        InputShape::Sections(_) => counts
+            .iter()
+            .enumerate()
+            .map(|(i, count)| format!("        assert_eq!(parsed.{i}.len(), {count});"))
+            .join("\n"),
+        _ => format!("        assert_eq!(parsed.len(), {});", counts[0]),
+    };
+
+    let test = format!(
+        "    fn sample_input() -> String {{
+        {}
+            .to_string()
+    }}
+
+    #[test]
+    fn can_parse_input() {{
+        let parsed = parse_input(&sample_input());
+        // TODO - replace with the expected values from the puzzle's example
+{assertions}
+    }}",
+        string_literal(&example)
+    );
+
+    ParseStub { function, test }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bootstrap_day::input_shape::*;
+    use crate::bootstrap_day::template::{render_file, TemplateValues, DAY_TEMPLATE};
+    use std::env::temp_dir;
+    use std::fs;
+    use std::process::Command;
+
+    /// Each day's example input, as used in its tests, which has the same format as the real input
+    fn example_fixtures() -> Vec<(u8, &'static str, InputShape)> {
+        use InputShape::*;
+
+        vec![
+            (
+                1,
+                "3   4
+4   3
+2   5
+1   3
+3   9
+3   3",
+                NumberLines {
+                    one_per_line: false,
+                },
+            ),
+            (
+                2,
+                "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+5 3 6 7 9",
+                NumberLines {
+                    one_per_line: false,
+                },
+            ),
+            (
+                3,
+                "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))",
+                Lines,
+            ),
+            (
+                4,
+                "..X...
+.SAMXM
+.A..A.
+XMAS.S
+.X....",
+                Grid { digits: false },
+            ),
+            (
+                5,
+                "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47",
+                Sections(vec![
+                    Pairs {
+                        separator: '|',
+                        numeric: true,
+                    },
+                    NumberLines {
+                        one_per_line: false,
+                    },
+                ]),
+            ),
+            (
+                6,
+                "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...",
+                Grid { digits: false },
+            ),
+            (
+                7,
+                "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20",
+                NumberLines {
+                    one_per_line: false,
+                },
+            ),
+            (
+                8,
+                "............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............",
+                Grid { digits: false },
+            ),
+            (9, "2333133121414131402", DigitString),
+            (
+                10,
+                "0123
+1234
+8765
+9876",
+                Grid { digits: true },
+            ),
+            (
+                11,
+                "0 1 10 99 999\n",
+                NumberLines {
+                    one_per_line: false,
+                },
+            ),
+            (
+                12,
+                "AAAA
+BBCD
+BBCC
+EEEC",
+                Grid { digits: false },
+            ),
+            (
+                13,
+                "Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279
+",
+                NumberBlocks,
+            ),
+            (
+                14,
+                "p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3
+",
+                NumberLines {
+                    one_per_line: false,
+                },
+            ),
+            (
+                15,
+                "########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<",
+                Sections(vec![Grid { digits: false }, Lines]),
+            ),
+            (
+                16,
+                "###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############",
+                Grid { digits: false },
+            ),
+            (
+                17,
+                "Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0",
+                Sections(vec![Lines, Lines]),
+            ),
+            (
+                18,
+                "5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0
+",
+                Pairs {
+                    separator: ',',
+                    numeric: true,
+                },
+            ),
+            (
+                19,
+                "r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb
+",
+                Sections(vec![Lines, Lines]),
+            ),
+            (
+                20,
+                "###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############
+",
+                Grid { digits: false },
+            ),
+            (
+                21,
+                "029A
+980A
+179A
+456A
+379A
+",
+                Lines,
+            ),
+            (
+                22,
+                "1
+10
+100
+2024
+",
+                NumberLines { one_per_line: true },
+            ),
+            (
+                23,
+                "kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn
+",
+                Pairs {
+                    separator: '-',
+                    numeric: false,
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn can_classify_example_inputs() {
+        for (day, input, expected) in example_fixtures() {
+            assert_eq!(classify(input), expected, "day {day}");
+        }
+    }
+
+    #[test]
+    fn can_generate_parse_stub() {
+        let stub = parse_stub("3   4\n4   3\n2   5\n1   3\n");
+
+        assert_eq!(
+            stub.function,
+            "/// Parse the puzzle input, e.g.
+///
+/// ```text
+/// 3   4
+/// 4   3
+/// 2   5
+/// ```
+fn parse_input(input: &String) -> Vec<Vec<i64>> {
+    input.lines().map(parse_numbers).collect()
+}
+
+/// Extract the integers from some text, e.g. `p=0,4 v=3,-3` becomes `[0, 4, 3, -3]`
+fn parse_numbers(text: &str) -> Vec<i64> {
+    text.split(|c: char| !c.is_ascii_digit() && c != '-')
+        .filter_map(|number| number.parse().ok())
+        .collect()
+}"
+        );
+        assert_eq!(
+            stub.test,
+            "    fn sample_input() -> String {
+        \"3   4\n4   3\n2   5\"
+            .to_string()
+    }
+
+    #[test]
+    fn can_parse_input() {
+        let parsed = parse_input(&sample_input());
+        // TODO - replace with the expected values from the puzzle's example
+        assert_eq!(parsed.len(), 3);
+    }"
+        );
+    }
+
+    #[test]
+    fn can_generate_parse_stub_for_sections() {
+        let stub = parse_stub("47|53\n97|13\n\n75,47,61\n97,61,53\n75,29,13\n61,13,29\n");
+
+        assert!(stub.function.contains(
+            "fn parse_input(input: &String) -> (Vec<(i64, i64)>, Vec<Vec<i64>>) {
+    let mut sections = input.split(\"\\n\\n\");
+    let section_1 = sections.next().unwrap();
+    let section_2 = sections.next().unwrap();
+
+    (
+        section_1.lines().flat_map(|line| line.split_once('|')).map(|(a, b)| (a.parse().unwrap(), b.parse().unwrap())).collect(),
+        section_2.lines().map(parse_numbers).collect(),
+    )
+}"
+        ));
+        assert!(stub.function.contains("/// 97|13\n///\n/// 75,47,61\n"));
+        assert!(stub.test.ends_with(
+            "        assert_eq!(parsed.0.len(), 2);
+        assert_eq!(parsed.1.len(), 3);
+    }"
+        ));
+    }
+
+    #[test]
+    fn can_escape_sample_input() {
+        assert_eq!(string_literal(r#"a"b\c"#), r#""a\"b\\c""#);
+        assert!(parse_stub("say \"hi\"\n").test.contains(r#""say \"hi\"""#));
+    }
+
+    #[test]
+    fn can_compile_and_run_rendered_stubs() {
+        let root = temp_dir().join(format!("aoc-parse-stub-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+
+        // Each example is scaffolded as its own day, with a stand-in for the runner the template uses
+        let mut main = "mod runner {
+    pub struct Runner;
+
+    impl Runner {
+        pub fn input(&mut self) -> String {
+            String::new()
+        }
+    }
+}
+
+fn main() {}
+"
+        .to_string();
+        for (day, input, _) in example_fixtures() {
+            let values = TemplateValues {
+                day,
+                title: format!("Example {day}"),
+                year: 2024,
+                parse_stub: parse_stub(input),
+            };
+            let rendered = render_file(DAY_TEMPLATE, &values).unwrap();
+            fs::write(root.join(format!("day_{day}.rs")), rendered).unwrap();
+            main.push_str(&format!("\n#[allow(dead_code)]\nmod day_{day};\n"));
+        }
+        fs::write(root.join("main.rs"), main).unwrap();
+
+        let compiled = Command::new("rustc")
+            .args(["--edition", "2021", "--test", "main.rs", "-o", "stubs"])
+            .current_dir(&root)
+            .output()
+            .unwrap();
+        assert!(
+            compiled.status.success(),
+            "{}",
+            String::from_utf8_lossy(&compiled.stderr)
+        );
+
+        let tests = Command::new(root.join("stubs")).output().unwrap();
+        assert!(
+            tests.status.success(),
+            "{}",
+            String::from_utf8_lossy(&tests.stdout)
+        );
+        assert!(String::from_utf8_lossy(&tests.stdout).contains("23 passed"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,53 +1,130 @@
+mod calendar;
+mod client;
+mod fetch;
+mod input_shape;
+mod personal_stats;
+mod sync;
+mod template;
+
+use crate::stars::Stars;
+use crate::YEAR;
+use calendar::parse_calendar;
+pub use client::SubmissionOutcome;
+use client::{HttpPuzzleClient, PuzzleClient};
 use error_chain::error_chain;
+use fetch::wait_for_unlock;
+use input_shape::parse_stub;
 use itertools::Itertools;
+use personal_stats::{parse_personal_stats, stats_json, STATS_PATH};
 use regex::Regex;
-use reqwest::cookie::Jar;
-use reqwest::Url;
 use scraper::{Html, Selector};
-use std::fs;
 use std::fs::File;
 use std::io::copy;
-use std::sync::Arc;
+use std::path::Path;
+use std::time::SystemTime;
+use sync::{format_table, sync_days};
+use template::{render_file, TemplateValues, BLOG_POST_TEMPLATE, DAY_TEMPLATE};
 
 error_chain! {
      foreign_links {
          Io(std::io::Error);
          HttpRequest(reqwest::Error);
      }
+
+     errors {
+         UnknownPlaceholder(name: String) {
+             description("unknown template placeholder")
+             display(
+                 "unknown template placeholder '{{{{{}}}}}', expected one of: {}",
+                 name,
+                 template::PLACEHOLDERS.join(", ")
+             )
+         }
+         UnclosedPlaceholder(offset: usize) {
+             description("unclosed template placeholder")
+             display("unclosed template placeholder at byte {}", offset)
+         }
+         NotFound(url: String) {
+             description("page not found")
+             display("{} was not found", url)
+         }
+         NotYetUnlocked(url: String) {
+             description("puzzle not yet unlocked")
+             display("{} has not unlocked yet", url)
+         }
+         NotLoggedIn(url: String) {
+             description("not logged in")
+             display("{} requires logging in, check res/session_cookie.txt", url)
+         }
+         UnexpectedStatus(url: String, status: u16) {
+             description("unexpected response status")
+             display("{} returned status {}", url, status)
+         }
+     }
 }
 
-pub fn bootstrap_day(day: u8) -> Result<()> {
-    let session_cookie =
-        fs::read_to_string("res/session_cookie.txt").expect("Failed to read session cookie");
+/// Download the input for a day, and scaffold the files for its solution and write-up.
+///
+/// If `wait` is set and the day hasn't unlocked yet, this counts down until it does, then retries with backoff any
+/// requests that fail because the day is still locked. Otherwise a locked or missing day fails straight away.
+pub fn bootstrap_day(day: u8, wait: bool) -> Result<()> {
+    let mut client = HttpPuzzleClient::from_session_file()?;
+    if wait {
+        wait_for_unlock(day);
+        client = client.retrying_until_unlocked();
+    }
 
-    let cookie = format!("session={}; Domain=adventofcode.com", session_cookie);
-    let url = "https://www.adventofcode.com".parse::<Url>().unwrap();
+    scaffold_day(&client, day, Path::new("."))
+}
 
-    let jar = Jar::default();
-    jar.add_cookie_str(cookie.as_str(), &url);
+/// Fetch the inputs and puzzle pages for every day that has unlocked, skipping those already downloaded, and print a
+/// table of what changed
+pub fn sync_all() -> Result<()> {
+    let client = HttpPuzzleClient::from_session_file()?;
+    let rows = sync_days(&client, Path::new("."), SystemTime::now());
+    print!("{}", format_table(&rows));
 
-    let client = reqwest::blocking::Client::builder()
-        .cookie_store(true)
-        .cookie_provider(Arc::new(jar))
-        .build()?;
+    Ok(())
+}
 
-    let input_target = format!("https://www.adventofcode.com/2024/day/{}/input", day);
-    let input_file_contents = client.get(input_target).send()?.text()?;
+/// Read the stars earned for each day from the calendar page, and save them to `stars`
+pub fn fetch_stars(stars: &mut Stars) -> Result<()> {
+    update_stars(&HttpPuzzleClient::from_session_file()?, stars)
+}
 
-    let output_filename = format!("res/day-{}-input.txt", day);
-    let mut output_file = File::create(output_filename.clone())?;
-    copy(&mut input_file_contents.as_bytes(), &mut output_file)?;
+fn update_stars(client: &impl PuzzleClient, stars: &mut Stars) -> Result<()> {
+    stars.replace(parse_calendar(&client.fetch_calendar_page()?));
+    Ok(stars.save()?)
+}
+
+/// Read the completion times and ranks from the personal leaderboard page into the data for the `pubs` site
+pub fn import_personal_stats() -> Result<()> {
+    import_stats(&HttpPuzzleClient::from_session_file()?, Path::new("."))
+}
 
-    println!("Puzzle input saved to {}", output_filename);
+fn import_stats(client: &impl PuzzleClient, root: &Path) -> Result<()> {
+    let stats = parse_personal_stats(&client.fetch_personal_stats_page()?);
+    println!("Found stats for {} days", stats.len());
+    write_file(
+        root,
+        STATS_PATH,
+        &stats_json(&stats),
+        "Personal stats saved to",
+    )
+}
 
-    let puzzle_target = format!("https://www.adventofcode.com/2024/day/{}", day);
-    let input_file_contents = client.get(puzzle_target).send()?.text()?;
+/// Submit an answer to the live site
+pub fn submit_answer(day: u8, part: u8, answer: &str) -> Result<SubmissionOutcome> {
+    HttpPuzzleClient::from_session_file()?.submit_answer(day, part, answer)
+}
 
-    let html = Html::parse_document(input_file_contents.as_str());
+/// Extract the puzzle's title from the `<h2>` of the puzzle page, e.g. `--- Day 1: Historian Hysteria ---`
+fn parse_title(puzzle_page: &str) -> Option<String> {
+    let html = Html::parse_document(puzzle_page);
     let selector = Selector::parse("article.day-desc > h2").unwrap();
     let pattern = Regex::new(r"^--- Day \d+: (?<title>.*) ---$").unwrap();
-    let title = html
-        .select(&selector)
+
+    html.select(&selector)
         .next()
         .map(|h2| h2.text().join(""))
         .and_then(|text| {
@@ -57,57 +134,177 @@ pub fn bootstrap_day(day: u8) -> Result<()> {
                 .ok()
                 .and_then(|caps| caps.name("title").map(|m| m.as_str().to_string()))
         })
-        .unwrap_or("???".to_string())
-        .to_string();
+}
+
+/// Write `contents` to `root`/`filename`, reporting the file written
+fn write_file(root: &Path, filename: &str, contents: &str, description: &str) -> Result<()> {
+    let mut file = File::create(root.join(filename))?;
+    copy(&mut contents.as_bytes(), &mut file)?;
+
+    println!("{description} {filename}");
+
+    Ok(())
+}
+
+/// Fetch the input and puzzle page for `day` using `client`, and write the input, solution, and blog post files
+/// under `root`. The templates are also read from `root`.
+fn scaffold_day(client: &impl PuzzleClient, day: u8, root: &Path) -> Result<()> {
+    let input_file_contents = client.fetch_input(day)?;
+    write_file(
+        root,
+        &format!("res/day-{}-input.txt", day),
+        &input_file_contents,
+        "Puzzle input saved to",
+    )?;
+
+    let puzzle_page = client.fetch_puzzle_page(day)?;
+    let title = parse_title(&puzzle_page).unwrap_or("???".to_string());
 
     println!("Title: {title}");
 
-    let rust_filename = format!("src/day_{}.rs", day);
-    let rust_contents = format!("\
-//! This is my solution for [Advent of Code - Day {day}: _{title}_](https://adventofcode.com/2024/day/{day})
-//!
-//!
+    let values = TemplateValues {
+        day,
+        title,
+        year: YEAR,
+        parse_stub: parse_stub(&input_file_contents),
+    };
 
-use std::fs;
+    let rust_contents = render_file(root.join(DAY_TEMPLATE), &values)?;
+    write_file(
+        root,
+        &format!("src/day_{}.rs", day),
+        &rust_contents,
+        "Rust file written",
+    )?;
 
-/// The entry point for running the solutions with the 'real' puzzle input.
-///
-/// - The puzzle input is expected to be at `<project_root>/res/day-{day}-input`
-/// - It is expected this will be called by [`super::main()`] when the user elects to run day {day}.
-pub fn run() {{
-    let _contents = fs::read_to_string(\"res/day-{day}-input.txt\").expect(\"Failed to read file\");
-}}
+    let markdown_contents = render_file(root.join(BLOG_POST_TEMPLATE), &values)?;
+    write_file(
+        root,
+        &format!("pubs/blog/day_{}.md", day),
+        &markdown_contents,
+        "Blog file written",
+    )?;
+
+    // TODO - modify main.rs
+
+    Ok(())
+}
 
 #[cfg(test)]
-mod tests {{
+mod tests {
+    use crate::bootstrap_day::client::FakePuzzleClient;
+    use crate::bootstrap_day::*;
+    use std::env::temp_dir;
+    use std::fs;
+    use std::path::PathBuf;
 
-}}",
-        day=day
-    );
+    const PUZZLE_PAGE: &str = "<html><body><main>\
+        <article class=\"day-desc\"><h2>--- Day 24: Crossed Wires ---</h2><p>You and The Historians...</p></article>\
+        </main></body></html>";
 
-    let mut rust_file = File::create(rust_filename.clone())?;
-    copy(&mut rust_contents.as_bytes(), &mut rust_file)?;
+    #[test]
+    fn can_parse_title() {
+        assert_eq!(parse_title(PUZZLE_PAGE), Some("Crossed Wires".to_string()));
+        assert_eq!(parse_title("<html><body>Not a puzzle</body></html>"), None);
+    }
 
-    println!("Rust file written {}", rust_filename);
+    /// A project root with the templates, and a directory of canned responses for [`FakePuzzleClient`]
+    fn setup(name: &str) -> (PathBuf, PathBuf) {
+        let root = temp_dir().join(format!("aoc-scaffold-{name}-{}", std::process::id()));
+        let canned = root.join("canned");
+        for dir in ["res", "src", "pubs/blog", "templates", "canned"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for template in [DAY_TEMPLATE, BLOG_POST_TEMPLATE] {
+            fs::copy(template, root.join(template)).unwrap();
+        }
 
-    let markdown_filename = format!("pubs/blog/day_{}.md", day);
-    let markdown_contents = format!(
-        "\
----
-day: {day}
-tags: [post]
-header: 'Day {day}: {title}'
----
-",
-        day = day
-    );
+        (root, canned)
+    }
 
-    let mut markdown_file = File::create(markdown_filename.clone())?;
-    copy(&mut markdown_contents.as_bytes(), &mut markdown_file)?;
+    #[test]
+    fn can_scaffold_day() {
+        let (root, canned) = setup("ok");
+        fs::write(canned.join("day-24-input.txt"), "x00: 1\nx01: 0\n").unwrap();
+        fs::write(canned.join("day-24-puzzle.html"), PUZZLE_PAGE).unwrap();
 
-    println!("Blog file written {}", markdown_filename);
+        scaffold_day(&FakePuzzleClient::new(&canned), 24, &root).unwrap();
 
-    // TODO - modify main.rs
+        assert_eq!(
+            fs::read_to_string(root.join("res/day-24-input.txt")).unwrap(),
+            "x00: 1\nx01: 0\n"
+        );
 
-    Ok(())
+        let rust = fs::read_to_string(root.join("src/day_24.rs")).unwrap();
+        assert!(rust.starts_with(
+            "//! This is my solution for [Advent of Code - Day 24: _Crossed Wires_](https://adventofcode.com/2024/day/24)"
+        ));
+        assert!(rust.contains("pub fn run(runner: &mut Runner) {"));
+        assert!(rust.contains("fn parse_input(input: &String) -> Vec<String> {"));
+        assert!(rust.contains("        assert_eq!(parsed.len(), 2);"));
+
+        let markdown = fs::read_to_string(root.join("pubs/blog/day_24.md")).unwrap();
+        assert_eq!(
+            markdown,
+            "---\nday: 24\ntags: [post]\nheader: 'Day 24: Crossed Wires'\n---\n"
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn does_not_write_files_for_missing_input() {
+        let (root, canned) = setup("missing");
+
+        let result = scaffold_day(&FakePuzzleClient::new(&canned), 24, &root);
+
+        assert!(matches!(result.unwrap_err().kind(), ErrorKind::NotFound(_)));
+        assert!(!root.join("res/day-24-input.txt").exists());
+        assert!(!root.join("src/day_24.rs").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn can_update_stars_from_calendar() {
+        let (root, canned) = setup("stars");
+        fs::write(
+            canned.join("calendar.html"),
+            "<pre class=\"calendar\"><a class=\"calendar-day1 calendar-verycomplete\">1</a>\
+            <a class=\"calendar-day2\">2</a></pre>",
+        )
+        .unwrap();
+
+        let path = root.join("res/stars.json");
+        let mut stars = Stars::new(&path);
+        update_stars(&FakePuzzleClient::new(&canned), &mut stars).unwrap();
+
+        assert_eq!(stars.get(1), Some(2));
+        assert_eq!(stars.get(2), Some(0));
+        assert_eq!(Stars::load(&path).unwrap(), stars);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn can_import_personal_stats() {
+        let (root, canned) = setup("stats");
+        fs::create_dir_all(root.join("pubs/_data")).unwrap();
+        fs::write(
+            canned.join("personal-stats.html"),
+            "<html><body><main><pre>\
+            Day       Time   Rank  Score       Time   Rank  Score\n  \
+            1   00:05:12    847      0          -      -      -\n\
+            </pre></main></body></html>",
+        )
+        .unwrap();
+
+        import_stats(&FakePuzzleClient::new(&canned), &root).unwrap();
+
+        let json = fs::read_to_string(root.join(STATS_PATH)).unwrap();
+        assert!(json.contains("\"time\": \"00:05:12\""));
+        assert!(json.contains("\"part_2\": null"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
 }
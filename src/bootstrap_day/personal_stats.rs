@@ -0,0 +1,179 @@
+//! Reading the completion time and rank for each day and part from the personal leaderboard page, so they can be shown
+//! next to each write-up in `pubs`.
+//!
+//! The page has a preformatted table with a row per attempted day, newest first, e.g.
+//!
+//! ```text
+//!       --------Part 1--------   --------Part 2--------
+//! Day       Time   Rank  Score       Time   Rank  Score
+//!   2   00:09:18   2861      0   00:17:54   2041      0
+//!   1   00:05:12   2547      0          -      -      -
+//! ```
+//!
+//! Days that haven't been attempted have no row, and a part that hasn't been completed is shown as dashes.
+
+use scraper::{Html, Selector};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Where the stats are written, as data for the `pubs` site
+pub const STATS_PATH: &str = "pubs/_data/stats.json";
+
+/// How long a part took to complete from when it unlocked, and the resulting leaderboard position
+#[derive(Eq, PartialEq, Debug, Clone, Serialize)]
+pub struct PartStats {
+    /// As shown on the site, `HH:MM:SS`, or `>24h`
+    pub time: String,
+    pub rank: u32,
+    pub score: u32,
+}
+
+/// The stats for both parts of a day that has been attempted
+#[derive(Eq, PartialEq, Debug, Clone, Serialize)]
+pub struct DayStats {
+    pub part_1: Option<PartStats>,
+    pub part_2: Option<PartStats>,
+}
+
+fn parse_part(columns: &[&str]) -> Option<PartStats> {
+    match columns {
+        [time, rank, score] => Some(PartStats {
+            time: time.to_string(),
+            rank: rank.parse().ok()?,
+            score: score.parse().ok()?,
+        }),
+        _ => None,
+    }
+}
+
+/// Parse a row of the table, ignoring the headings
+fn parse_row(line: &str) -> Option<(u8, DayStats)> {
+    let columns: Vec<&str> = line.split_whitespace().collect();
+    if columns.len() != 7 {
+        return None;
+    }
+
+    let day = columns[0].parse().ok()?;
+    Some((
+        day,
+        DayStats {
+            part_1: parse_part(&columns[1..4]),
+            part_2: parse_part(&columns[4..7]),
+        },
+    ))
+}
+
+/// The stats for each day that has been attempted, from the personal leaderboard page
+pub fn parse_personal_stats(html: &str) -> BTreeMap<u8, DayStats> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("main pre").unwrap();
+
+    document
+        .select(&selector)
+        .flat_map(|pre| {
+            pre.text()
+                .collect::<String>()
+                .lines()
+                .filter_map(parse_row)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Format the stats as the JSON data file for `pubs`, keyed by day
+pub fn stats_json(stats: &BTreeMap<u8, DayStats>) -> String {
+    serde_json::to_string_pretty(stats).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bootstrap_day::personal_stats::*;
+
+    /// Saved from the personal leaderboard page part way through the event, trimmed to the first few days
+    const PERSONAL_STATS: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<head>
+<meta charset="utf-8"/>
+<title>Personal Leaderboard Statistics - Advent of Code 2024</title>
+</head>
+<body>
+<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1></div></header>
+<main>
+<article><p>These are your personal leaderboard statistics. <em>Rank</em> is your position on that leaderboard:
+1st place corresponds to Rank 1, 100th place corresponds to Rank 100, and so on. If you didn't get a score,
+you still have a rank, but your <em>score</em> is zero.</p>
+<pre><span class="leaderboard-daydesc-first">      --------Part 1--------   </span><span class="leaderboard-daydesc-both">--------Part 2--------</span>
+Day <span class="leaderboard-daydesc-first">      Time   Rank  Score</span>   <span class="leaderboard-daydesc-both">      Time   Rank  Score</span>
+  5   &gt;24h  61337      0          -      -      -
+  3   00:06:45   1784      0   00:15:02   2255      0
+  2   00:09:18   2861      0   00:17:54   2041      0
+  1   00:05:12    847      0   00:07:25    912      0
+</pre>
+</article>
+</main>
+</body>
+</html>
+"#;
+
+    fn part(time: &str, rank: u32) -> Option<PartStats> {
+        Some(PartStats {
+            time: time.to_string(),
+            rank,
+            score: 0,
+        })
+    }
+
+    #[test]
+    fn can_parse_personal_stats() {
+        let stats = parse_personal_stats(PERSONAL_STATS);
+
+        assert_eq!(stats.keys().copied().collect::<Vec<_>>(), vec![1, 2, 3, 5]);
+        assert_eq!(
+            stats[&1],
+            DayStats {
+                part_1: part("00:05:12", 847),
+                part_2: part("00:07:25", 912),
+            }
+        );
+        assert_eq!(
+            stats[&5],
+            DayStats {
+                part_1: part(">24h", 61337),
+                part_2: None,
+            }
+        );
+        assert!(!stats.contains_key(&4));
+    }
+
+    #[test]
+    fn can_parse_page_with_no_attempts() {
+        let html = "<html><body><main><article><p>You haven't collected any stars.</p></article></main></body>\
+            </html>";
+        assert!(parse_personal_stats(html).is_empty());
+    }
+
+    #[test]
+    fn can_format_stats_json() {
+        let stats = BTreeMap::from([(
+            5,
+            DayStats {
+                part_1: part(">24h", 61337),
+                part_2: None,
+            },
+        )]);
+
+        assert_eq!(
+            stats_json(&stats),
+            r#"{
+  "5": {
+    "part_1": {
+      "time": ">24h",
+      "rank": 61337,
+      "score": 0
+    },
+    "part_2": null
+  }
+}"#
+        );
+    }
+}
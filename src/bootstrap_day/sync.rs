@@ -0,0 +1,216 @@
+//! Bulk download of the inputs and puzzle pages for every day that has unlocked, e.g. when setting up a new machine.
+//!
+//! Files already on disk are skipped, and each file is written to a temporary file and then renamed into place, so an
+//! interrupted sync leaves no partial files and can be resumed by running it again. A failure for one day is recorded
+//! and the sync carries on with the next.
+
+use super::client::PuzzleClient;
+use super::fetch::unlock_time;
+use super::Result;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// What happened to one file during the sync
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum SyncStatus {
+    Fetched,
+    AlreadyPresent,
+    Failed(String),
+}
+
+impl Display for SyncStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyncStatus::Fetched => write!(f, "fetched"),
+            SyncStatus::AlreadyPresent => write!(f, "present"),
+            SyncStatus::Failed(reason) => write!(f, "failed: {reason}"),
+        }
+    }
+}
+
+/// The outcome of syncing one day
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct SyncRow {
+    pub day: u8,
+    pub input: SyncStatus,
+    pub puzzle_page: SyncStatus,
+}
+
+/// Where a day's input is stored
+pub fn input_path(root: &Path, day: u8) -> PathBuf {
+    root.join(format!("res/day-{day}-input.txt"))
+}
+
+/// Where a day's puzzle page is stored
+pub fn puzzle_page_path(root: &Path, day: u8) -> PathBuf {
+    root.join(format!("res/day-{day}-puzzle.html"))
+}
+
+/// The days that have unlocked at `now`
+pub fn unlocked_days(now: SystemTime) -> Vec<u8> {
+    (1..=25).filter(|&day| unlock_time(day) <= now).collect()
+}
+
+/// Write via a temporary file so that an interrupted write doesn't leave a partial file that would be skipped later
+fn write_atomically(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temporary = path.with_extension("partial");
+    fs::write(&temporary, contents)?;
+    fs::rename(&temporary, path)
+}
+
+/// Fetch and store a file, unless it is already present
+fn sync_file(path: &Path, fetch: impl FnOnce() -> Result<String>) -> SyncStatus {
+    if path.exists() {
+        return SyncStatus::AlreadyPresent;
+    }
+
+    match fetch().and_then(|contents| Ok(write_atomically(path, &contents)?)) {
+        Ok(()) => SyncStatus::Fetched,
+        Err(e) => SyncStatus::Failed(e.to_string()),
+    }
+}
+
+/// Fetch any missing inputs and puzzle pages for the days unlocked at `now`, storing them under `root`
+pub fn sync_days(client: &impl PuzzleClient, root: &Path, now: SystemTime) -> Vec<SyncRow> {
+    unlocked_days(now)
+        .into_iter()
+        .map(|day| SyncRow {
+            day,
+            input: sync_file(&input_path(root, day), || client.fetch_input(day)),
+            puzzle_page: sync_file(&puzzle_page_path(root, day), || {
+                client.fetch_puzzle_page(day)
+            }),
+        })
+        .collect()
+}
+
+/// Render the outcome of a sync as a table, with a summary of what changed
+pub fn format_table(rows: &[SyncRow]) -> String {
+    let mut table = format!("{:>3}  {:<10}  {}\n", "Day", "Input", "Puzzle page");
+    for SyncRow {
+        day,
+        input,
+        puzzle_page,
+    } in rows
+    {
+        table.push_str(&format!(
+            "{:>3}  {:<10}  {}\n",
+            day,
+            input.to_string(),
+            puzzle_page
+        ));
+    }
+
+    let statuses = || rows.iter().flat_map(|row| [&row.input, &row.puzzle_page]);
+    let fetched = statuses()
+        .filter(|&status| *status == SyncStatus::Fetched)
+        .count();
+    let failed = statuses()
+        .filter(|status| matches!(status, SyncStatus::Failed(_)))
+        .count();
+    table.push_str(&format!("{fetched} files fetched, {failed} failed\n"));
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bootstrap_day::client::FakePuzzleClient;
+    use crate::bootstrap_day::sync::*;
+    use std::env::temp_dir;
+    use std::time::Duration;
+
+    #[test]
+    fn can_find_unlocked_days() {
+        let day_3 = unlock_time(3);
+        assert_eq!(unlocked_days(day_3 - Duration::from_secs(1)), vec![1, 2]);
+        assert_eq!(unlocked_days(day_3), vec![1, 2, 3]);
+        assert!(unlocked_days(unlock_time(1) - Duration::from_secs(1)).is_empty());
+        assert_eq!(unlocked_days(SystemTime::now()).len(), 25);
+    }
+
+    #[test]
+    fn can_sync_missing_files() {
+        let root = temp_dir().join(format!("aoc-sync-{}", std::process::id()));
+        let canned = root.join("canned");
+        fs::create_dir_all(root.join("res")).unwrap();
+        fs::create_dir_all(&canned).unwrap();
+
+        fs::write(canned.join("day-1-input.txt"), "3   4\n").unwrap();
+        fs::write(canned.join("day-1-puzzle.html"), "<h2>Day 1</h2>").unwrap();
+        fs::write(canned.join("day-2-input.txt"), "7 6 4 2 1\n").unwrap();
+        fs::write(canned.join("day-3-puzzle.html"), "<h2>Day 3</h2>").unwrap();
+        fs::write(input_path(&root, 1), "already here\n").unwrap();
+
+        let client = FakePuzzleClient::new(&canned);
+        let rows = sync_days(&client, &root, unlock_time(3));
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0],
+            SyncRow {
+                day: 1,
+                input: SyncStatus::AlreadyPresent,
+                puzzle_page: SyncStatus::Fetched
+            }
+        );
+        assert_eq!(rows[1].input, SyncStatus::Fetched);
+        assert!(matches!(rows[1].puzzle_page, SyncStatus::Failed(_)));
+        assert!(matches!(rows[2].input, SyncStatus::Failed(_)));
+        assert_eq!(rows[2].puzzle_page, SyncStatus::Fetched);
+
+        assert_eq!(
+            fs::read_to_string(input_path(&root, 1)).unwrap(),
+            "already here\n"
+        );
+        assert_eq!(
+            fs::read_to_string(input_path(&root, 2)).unwrap(),
+            "7 6 4 2 1\n"
+        );
+        assert!(!root.join("res/day-2-input.partial").exists());
+
+        // Resuming only fetches what is still missing
+        fs::write(canned.join("day-2-puzzle.html"), "<h2>Day 2</h2>").unwrap();
+        let rows = sync_days(&client, &root, unlock_time(3));
+        assert_eq!(
+            rows[1],
+            SyncRow {
+                day: 2,
+                input: SyncStatus::AlreadyPresent,
+                puzzle_page: SyncStatus::Fetched
+            }
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn can_format_table() {
+        let rows = vec![
+            SyncRow {
+                day: 1,
+                input: SyncStatus::AlreadyPresent,
+                puzzle_page: SyncStatus::Fetched,
+            },
+            SyncRow {
+                day: 12,
+                input: SyncStatus::Fetched,
+                puzzle_page: SyncStatus::Failed("not found".to_string()),
+            },
+        ];
+
+        assert_eq!(
+            format_table(&rows),
+            "Day  Input       Puzzle page
+  1  present     fetched
+ 12  fetched     failed: not found
+2 files fetched, 1 failed
+"
+        );
+    }
+}
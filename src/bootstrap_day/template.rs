@@ -0,0 +1,155 @@
+//! Renders the scaffold templates in `<project_root>/templates`. These are read at runtime so that they can be edited
+//! without recompiling.
+//!
+//! Placeholders are written as `{{name}}`. The available names are listed in [`PLACEHOLDERS`], and an unknown name is
+//! treated as an error so that typos in a template are caught rather than being written into the new files.
+
+use super::input_shape::ParseStub;
+use super::{ErrorKind, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// The template for `src/day_N.rs`
+pub const DAY_TEMPLATE: &str = "templates/day.rs.tpl";
+/// The template for `pubs/blog/day_N.md`
+pub const BLOG_POST_TEMPLATE: &str = "templates/blog_post.md.tpl";
+
+/// The names that can be used as placeholders in a template
+pub const PLACEHOLDERS: [&str; 7] = [
+    "day",
+    "title",
+    "year",
+    "puzzle_url",
+    "input_path",
+    "parse_input",
+    "parse_test",
+];
+
+/// The values to substitute for each placeholder
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct TemplateValues {
+    pub day: u8,
+    pub title: String,
+    pub year: u16,
+    /// The generated parser for the day's input, see [`super::input_shape::parse_stub`]
+    pub parse_stub: ParseStub,
+}
+
+impl TemplateValues {
+    fn lookup(&self) -> HashMap<&'static str, String> {
+        HashMap::from([
+            ("day", self.day.to_string()),
+            ("title", self.title.clone()),
+            ("year", self.year.to_string()),
+            (
+                "puzzle_url",
+                format!("https://adventofcode.com/{}/day/{}", self.year, self.day),
+            ),
+            ("input_path", format!("res/day-{}-input.txt", self.day)),
+            ("parse_input", self.parse_stub.function.clone()),
+            ("parse_test", self.parse_stub.test.clone()),
+        ])
+    }
+}
+
+/// Substitute the `{{name}}` placeholders in `template`. Whitespace inside the braces is ignored.
+pub fn render(template: &str, values: &TemplateValues) -> Result<String> {
+    let lookup = values.lookup();
+    let mut output = String::with_capacity(template.len());
+    let mut remaining = template;
+
+    while let Some(start) = remaining.find("{{") {
+        output.push_str(&remaining[..start]);
+        let after_open = &remaining[start + 2..];
+        let end = after_open.find("}}").ok_or_else(|| {
+            ErrorKind::UnclosedPlaceholder(template.len() - remaining.len() + start)
+        })?;
+
+        let name = after_open[..end].trim();
+        let value = lookup
+            .get(name)
+            .ok_or_else(|| ErrorKind::UnknownPlaceholder(name.to_string()))?;
+        output.push_str(value);
+
+        remaining = &after_open[end + 2..];
+    }
+
+    output.push_str(remaining);
+
+    Ok(output)
+}
+
+/// Read a template file and render it
+pub fn render_file(path: impl AsRef<Path>, values: &TemplateValues) -> Result<String> {
+    let template = fs::read_to_string(path)?;
+    render(&template, values)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bootstrap_day::template::*;
+    use crate::bootstrap_day::Error;
+
+    fn example_values() -> TemplateValues {
+        TemplateValues {
+            day: 6,
+            title: "Guard Gallivant".to_string(),
+            year: 2024,
+            parse_stub: ParseStub::default(),
+        }
+    }
+
+    #[test]
+    fn can_render_placeholders() {
+        assert_eq!(
+            render(
+                "Day {{day}}: {{ title }} ({{year}}) {{puzzle_url}} {{input_path}}",
+                &example_values()
+            )
+            .unwrap(),
+            "Day 6: Guard Gallivant (2024) https://adventofcode.com/2024/day/6 res/day-6-input.txt"
+        );
+        assert_eq!(
+            render("fn run() {}", &example_values()).unwrap(),
+            "fn run() {}"
+        );
+    }
+
+    #[test]
+    fn can_reject_bad_placeholders() {
+        assert!(matches!(
+            render("{{dya}}", &example_values()),
+            Err(Error(ErrorKind::UnknownPlaceholder(name), _)) if name == "dya"
+        ));
+        assert!(matches!(
+            render("let x = {{day", &example_values()),
+            Err(Error(ErrorKind::UnclosedPlaceholder(8), _))
+        ));
+        assert_eq!(
+            render("{{dya}}", &example_values()).unwrap_err().to_string(),
+            "unknown template placeholder '{{dya}}', expected one of: day, title, year, puzzle_url, input_path, parse_input, parse_test"
+        );
+    }
+
+    #[test]
+    fn can_render_project_templates() {
+        let values = example_values();
+        for path in [DAY_TEMPLATE, BLOG_POST_TEMPLATE] {
+            assert!(render_file(path, &values).is_ok(), "{path} should render");
+        }
+
+        assert!(render_file(BLOG_POST_TEMPLATE, &values)
+            .unwrap()
+            .contains("header: 'Day 6: Guard Gallivant'"));
+    }
+
+    #[test]
+    fn lookup_covers_all_placeholders() {
+        let lookup = example_values().lookup();
+        assert_eq!(lookup.len(), PLACEHOLDERS.len());
+        for name in PLACEHOLDERS {
+            assert!(lookup.contains_key(name), "{name} should have a value");
+        }
+    }
+}
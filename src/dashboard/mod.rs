@@ -0,0 +1,534 @@
+//! A full-screen terminal view of the whole event, with a row for each of the 25 days showing whether it has a
+//! module registered in [`crate::main`], whether its input is in `res/`, the cached answers for the current input,
+//! its stars, and how long the solutions took the last time they ran. The stars are those last read from the site with
+//! `--stars`, along with any [`StarFlag`], or if they've never been read, the number of confirmed answers.
+//!
+//! A day can be run, or bootstrapped if it doesn't have a module yet, and its output is shown in a pane below the
+//! table. These are run as a subprocess of the same executable, so that the output can be captured, and a panicking
+//! solution doesn't take down the dashboard. The status is re-read from `res/` afterwards.
+//!
+//! The terminal is drawn with ANSI escape codes, and put into unbuffered mode with `stty`, to avoid pulling in a
+//! terminal UI library. If stdout isn't a terminal, the table is printed once instead.
+
+use crate::answer_cache::{AnswerCache, CacheKey, CACHE_PATH};
+use crate::runner::Day;
+use crate::stars::{flag, StarFlag, Stars, STARS_PATH};
+use crate::YEAR;
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// The size to assume if the terminal doesn't report one
+const DEFAULT_SIZE: (usize, usize) = (40, 100);
+/// The lines taken by the title, table header, output separator, and key help
+const CHROME_LINES: usize = 4;
+/// The fewest lines of output to show when the terminal is too short for the whole table
+const MIN_OUTPUT_LINES: usize = 3;
+
+const REVERSE: &str = "\x1b[7m";
+const RESET: &str = "\x1b[0m";
+
+/// What is known about a day from the registry and the files in `res/`
+#[derive(Eq, PartialEq, Debug, Clone)]
+struct DayStatus {
+    day: u8,
+    module: bool,
+    input: bool,
+    answers: [Option<String>; 2],
+    confirmed: usize,
+    /// The stars shown on the site's calendar, if they have been read
+    site_stars: Option<u8>,
+    flag: Option<StarFlag>,
+    /// The total time the parts took the last time they were solved
+    duration: Option<Duration>,
+}
+
+/// A key press the dashboard responds to
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+enum Key {
+    Up,
+    Down,
+    Run,
+    ForceRun,
+    Bootstrap,
+    Quit,
+    Other,
+}
+
+fn parse_key(bytes: &[u8]) -> Key {
+    match bytes {
+        b"\x1b[A" | b"k" => Key::Up,
+        b"\x1b[B" | b"j" => Key::Down,
+        b"r" | b"\n" => Key::Run,
+        b"f" => Key::ForceRun,
+        b"b" => Key::Bootstrap,
+        b"q" | b"\x1b" => Key::Quit,
+        _ => Key::Other,
+    }
+}
+
+impl DayStatus {
+    fn stars(&self) -> usize {
+        self.site_stars.map_or(self.confirmed, usize::from)
+    }
+}
+
+fn day_status(
+    day: u8,
+    solution: Option<&Day>,
+    root: &Path,
+    cache: &AnswerCache,
+    stars: &Stars,
+) -> DayStatus {
+    let input = fs::read_to_string(root.join(format!("res/day-{day}-input.txt"))).ok();
+    let cached: Vec<_> = (1..=2)
+        .map(|part| {
+            solution.zip(input.as_ref()).and_then(|(solution, input)| {
+                cache.get(&CacheKey::new(day, part, input, solution.version))
+            })
+        })
+        .collect();
+
+    let durations: Vec<u64> = cached
+        .iter()
+        .flat_map(|cached| cached.and_then(|cached| cached.duration_us))
+        .collect();
+
+    DayStatus {
+        day,
+        module: solution.is_some(),
+        input: input.is_some(),
+        answers: [0, 1].map(|part| cached[part].map(|cached| cached.answer.clone())),
+        confirmed: cached
+            .iter()
+            .flatten()
+            .filter(|cached| cached.confirmed)
+            .count(),
+        site_stars: stars.get(day),
+        flag: stars.get(day).and_then(|stars| {
+            flag(
+                solution.is_some(),
+                stars,
+                [cached[0].is_some(), cached[1].is_some()],
+            )
+        }),
+        duration: (!durations.is_empty()).then(|| Duration::from_micros(durations.iter().sum())),
+    }
+}
+
+fn load_statuses(days: &[Day], root: &Path, cache: &AnswerCache, stars: &Stars) -> Vec<DayStatus> {
+    (1..=25)
+        .map(|day| {
+            let solution = days.iter().find(|solution| solution.day == day);
+            day_status(day, solution, root, cache, stars)
+        })
+        .collect()
+}
+
+/// Pad or cut `text` to exactly `width` characters
+fn fit(text: &str, width: usize) -> String {
+    format!("{:<width$.width$}", text)
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "-"
+    }
+}
+
+fn format_row(status: &DayStatus) -> String {
+    let answer = |part: usize| status.answers[part].as_deref().unwrap_or("-").to_string();
+    format!(
+        "{:>4}  {:<6}  {:<5}  {}  {}  {:<5}  {:<8}  {}",
+        status.day,
+        yes_no(status.module),
+        yes_no(status.input),
+        fit(&answer(0), 16),
+        fit(&answer(1), 16),
+        "*".repeat(status.stars()),
+        status
+            .duration
+            .map(|duration| format!("{duration:.2?}"))
+            .unwrap_or("-".to_string()),
+        status.flag.map(|flag| flag.to_string()).unwrap_or_default()
+    )
+}
+
+/// Draw the whole screen, with `message` as the title of the output pane. `size` is the terminal's (rows, columns)
+fn render(
+    statuses: &[DayStatus],
+    selected: usize,
+    output: &[String],
+    message: &str,
+    size: (usize, usize),
+) -> Vec<String> {
+    let (rows, columns) = size;
+    let available = rows.saturating_sub(CHROME_LINES);
+    let table_rows = statuses
+        .len()
+        .min(available.saturating_sub(MIN_OUTPUT_LINES))
+        .max(1);
+    let first = (selected + 1).saturating_sub(table_rows);
+    let stars: usize = statuses.iter().map(DayStatus::stars).sum();
+
+    let mut lines = vec![
+        format!("Advent of Code {YEAR} - {stars}/50 stars"),
+        format!(
+            "{:>4}  {:<6}  {:<5}  {}  {}  {:<5}  {:<8}  {}",
+            "Day",
+            "Module",
+            "Input",
+            fit("Part 1", 16),
+            fit("Part 2", 16),
+            "Stars",
+            "Time",
+            "Notes"
+        ),
+    ];
+
+    for (i, status) in statuses.iter().enumerate().skip(first).take(table_rows) {
+        let row = fit(&format_row(status), columns);
+        lines.push(if i == selected {
+            format!("{REVERSE}{row}{RESET}")
+        } else {
+            row
+        });
+    }
+
+    let title = if message.is_empty() {
+        "Output"
+    } else {
+        message
+    };
+    lines.push(fit(
+        &format!("---- {title} {}", "-".repeat(columns)),
+        columns,
+    ));
+
+    let output_rows = rows.saturating_sub(lines.len() + 1);
+    let skip = output.len().saturating_sub(output_rows);
+    lines.extend(output.iter().skip(skip).map(|line| fit(line, columns)));
+    lines.resize(rows.saturating_sub(1).max(lines.len()), String::new());
+
+    lines.push(fit(
+        "[up/down] select  [r] run  [f] force run  [b] bootstrap  [q] quit",
+        columns,
+    ));
+
+    lines
+}
+
+/// Run this executable with `args`, returning its combined stdout and stderr
+fn run_subprocess(args: &[String]) -> Vec<String> {
+    let output = env::current_exe()
+        .and_then(|exe| Command::new(exe).args(args).stdin(Stdio::null()).output());
+
+    match output {
+        Ok(output) => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .chain(String::from_utf8_lossy(&output.stderr).lines())
+            .map(|line| line.to_string())
+            .collect(),
+        Err(e) => vec![format!("Failed to run: {e}")],
+    }
+}
+
+/// Puts the terminal into unbuffered mode with the alternate screen, and restores it when dropped
+struct Terminal {
+    saved: Option<String>,
+}
+
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+impl Terminal {
+    fn enter() -> Terminal {
+        let saved = stty(&["-g"]);
+        stty(&["-icanon", "-echo", "min", "1"]);
+        print!("\x1b[?1049h\x1b[?25l");
+
+        Terminal { saved }
+    }
+
+    fn size(&self) -> (usize, usize) {
+        stty(&["size"])
+            .and_then(|size| {
+                let (rows, columns) = size.split_once(' ')?;
+                Some((rows.parse().ok()?, columns.parse().ok()?))
+            })
+            .filter(|&(rows, columns)| rows > 0 && columns > 0)
+            .unwrap_or(DEFAULT_SIZE)
+    }
+
+    fn draw(&self, lines: &[String]) {
+        print!("\x1b[H\x1b[2J{}", lines.join("\r\n"));
+        io::stdout().flush().unwrap();
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        io::stdout().flush().unwrap();
+        if let Some(saved) = &self.saved {
+            stty(&[saved]);
+        }
+    }
+}
+
+/// Read the current status of every day from the files in `res/`
+fn reload(days: &[Day]) -> Vec<DayStatus> {
+    let cache = AnswerCache::load(CACHE_PATH).expect("Failed to load answer cache");
+    let stars = Stars::load(STARS_PATH).expect("Failed to load stars");
+    load_statuses(days, Path::new("."), &cache, &stars)
+}
+
+/// Print the table of days once, without the rest of the dashboard
+pub fn print_table(days: &[Day]) {
+    let statuses = reload(days);
+    render(&statuses, 0, &[], "", (statuses.len() + CHROME_LINES, 200))
+        .iter()
+        .take(statuses.len() + 2)
+        .for_each(|line| {
+            println!(
+                "{}",
+                line.replace(REVERSE, "").replace(RESET, "").trim_end()
+            )
+        });
+}
+
+/// Show the dashboard until `q` is pressed
+pub fn dashboard(days: &[Day]) {
+    if !io::stdout().is_terminal() {
+        return print_table(days);
+    }
+
+    let mut statuses = reload(days);
+
+    let terminal = Terminal::enter();
+    let mut selected = 0;
+    let mut output: Vec<String> = Vec::new();
+    let mut message = String::new();
+    let mut buffer = [0; 8];
+
+    loop {
+        terminal.draw(&render(
+            &statuses,
+            selected,
+            &output,
+            &message,
+            terminal.size(),
+        ));
+
+        let read = io::stdin().read(&mut buffer).unwrap_or(0);
+        if read == 0 {
+            break;
+        }
+
+        let status = &statuses[selected];
+        let day = status.day.to_string();
+        let args = match parse_key(&buffer[..read]) {
+            Key::Up => {
+                selected = selected.saturating_sub(1);
+                None
+            }
+            Key::Down => {
+                selected = (selected + 1).min(statuses.len() - 1);
+                None
+            }
+            Key::Run | Key::ForceRun if !status.module => {
+                message = format!("Day {day} has no module yet, press b to bootstrap it");
+                None
+            }
+            Key::Run => Some(vec![day.clone()]),
+            Key::ForceRun => Some(vec![day.clone(), "--force".to_string()]),
+            Key::Bootstrap if status.module => {
+                message = format!("Day {day} already has a module");
+                None
+            }
+            Key::Bootstrap => Some(vec![day.clone()]),
+            Key::Quit => break,
+            Key::Other => None,
+        };
+
+        if let Some(args) = args {
+            message = format!("Running {}...", args.join(" "));
+            terminal.draw(&render(
+                &statuses,
+                selected,
+                &output,
+                &message,
+                terminal.size(),
+            ));
+
+            output = run_subprocess(&args);
+            statuses = reload(days);
+            message = format!("Finished day {day}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dashboard::*;
+    use std::env::temp_dir;
+
+    fn status(day: u8) -> DayStatus {
+        DayStatus {
+            day,
+            module: false,
+            input: false,
+            answers: [None, None],
+            confirmed: 0,
+            site_stars: None,
+            flag: None,
+            duration: None,
+        }
+    }
+
+    #[test]
+    fn can_load_day_statuses() {
+        let root = temp_dir().join(format!("aoc-dashboard-{}", std::process::id()));
+        fs::create_dir_all(root.join("res")).unwrap();
+        fs::write(root.join("res/day-1-input.txt"), "3   4").unwrap();
+        fs::write(root.join("res/day-3-input.txt"), "mul(2,4)").unwrap();
+
+        let days = vec![Day::new(1, "2", |_| ()), Day::new(2, "1", |_| ())];
+        let mut cache = AnswerCache::new("unused.json");
+        let key = CacheKey::new(1, 1, "3   4", "2");
+        cache.insert(key.clone(), "11".to_string());
+        cache.confirm(&key);
+        cache.record_duration(&key, Duration::from_millis(3));
+        cache.insert(CacheKey::new(1, 2, "3   4", "2"), "31".to_string());
+        // A stale answer from an older version
+        cache.insert(CacheKey::new(2, 1, "", "0"), "2".to_string());
+
+        let mut stars = Stars::new("unused.json");
+        stars.replace([(1, 2), (2, 0), (3, 1)].into());
+
+        let statuses = load_statuses(&days, &root, &cache, &stars);
+
+        assert_eq!(statuses.len(), 25);
+        assert_eq!(
+            statuses[0],
+            DayStatus {
+                day: 1,
+                module: true,
+                input: true,
+                answers: [Some("11".to_string()), Some("31".to_string())],
+                confirmed: 1,
+                site_stars: Some(2),
+                flag: None,
+                duration: Some(Duration::from_millis(3)),
+            }
+        );
+        assert_eq!(
+            statuses[1],
+            DayStatus {
+                module: true,
+                site_stars: Some(0),
+                flag: Some(StarFlag::NoStars),
+                ..status(2)
+            }
+        );
+        assert_eq!(
+            statuses[2],
+            DayStatus {
+                input: true,
+                site_stars: Some(1),
+                flag: Some(StarFlag::NoAnswer { part: 1 }),
+                ..status(3)
+            }
+        );
+        assert_eq!(statuses[24], status(25));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn can_parse_keys() {
+        assert_eq!(parse_key(b"\x1b[A"), Key::Up);
+        assert_eq!(parse_key(b"j"), Key::Down);
+        assert_eq!(parse_key(b"\n"), Key::Run);
+        assert_eq!(parse_key(b"f"), Key::ForceRun);
+        assert_eq!(parse_key(b"b"), Key::Bootstrap);
+        assert_eq!(parse_key(b"q"), Key::Quit);
+        assert_eq!(parse_key(b"x"), Key::Other);
+    }
+
+    #[test]
+    fn can_render_dashboard() {
+        let statuses: Vec<DayStatus> = (1..=3)
+            .map(|day| DayStatus {
+                module: day < 3,
+                input: day < 3,
+                answers: [Some(format!("{day}1")), None],
+                confirmed: if day == 1 { 2 } else { 0 },
+                site_stars: (day == 3).then_some(1),
+                flag: (day == 3).then_some(StarFlag::NoAnswer { part: 2 }),
+                duration: Some(Duration::from_micros(1500)),
+                ..status(day)
+            })
+            .collect();
+        let output = vec!["first".to_string(), "second".to_string()];
+
+        let lines = render(&statuses, 1, &output, "Finished day 2", (10, 100));
+
+        assert_eq!(lines.len(), 10);
+        assert_eq!(lines[0], "Advent of Code 2024 - 3/50 stars");
+        assert_eq!(
+            lines[2].trim_end(),
+            "   1  yes     yes    11                -                 **     1.50ms"
+        );
+        assert!(lines[3].starts_with(REVERSE));
+        assert!(lines[3].contains("   2  yes     yes    21"));
+        assert_eq!(
+            lines[4].trim_end(),
+            "   3  -       -      31                -                 *      1.50ms    no part 2 answer"
+        );
+        assert!(lines[5].starts_with("---- Finished day 2 ----"));
+        assert_eq!(
+            &lines[6..8],
+            &["first", "second"].map(|line| fit(line, 100))
+        );
+        assert!(lines[9].trim_end().ends_with("[q] quit"));
+        assert!(lines.iter().all(|line| line
+            .replace(REVERSE, "")
+            .replace(RESET, "")
+            .chars()
+            .count()
+            <= 100));
+    }
+
+    #[test]
+    fn can_scroll_table_in_short_terminal() {
+        let statuses: Vec<DayStatus> = (1..=25).map(status).collect();
+        let output: Vec<String> = (1..=10).map(|i| format!("line {i}")).collect();
+
+        let lines = render(&statuses, 20, &output, "", (12, 60));
+        assert!(lines[7].starts_with("---- Output ----"));
+
+        // 5 days are shown, ending with the selected day, then the last 3 lines of output
+        assert_eq!(lines.len(), 12);
+        assert!(lines[2].starts_with("  17"));
+        assert!(lines[6].starts_with(&format!("{REVERSE}  21")));
+        assert_eq!(
+            &lines[8..11],
+            &["line 8", "line 9", "line 10"].map(|line| fit(line, 60))
+        );
+    }
+}
@@ -3,26 +3,76 @@
 //! The input has been turned into a list of u32 for each column by [`parse_input`]. Part 1 is solved in two steps
 //! [`to_sorted_pairs`] sorts the lists and zips them together, then [`sum_diffs`] reduces the list of pairs to the
 //! puzzle solution. Part 2 is solved by [`sum_similarity_scores`].
+//!
+//! Zipping the sorted lists drops the extra ids when one list is longer than the other. With the `pairing` param set to
+//! `matching`, part 1 uses [`min_distance_matching`] instead, which pairs every id in the shorter list with one in the
+//! longer list so that the total distance is as small as possible, and leaves the rest unmatched.
+//!
+//! [`reconcile`] goes further than the two sums, listing where the lists disagree for `--reconcile`, which is written
+//! as a table, CSV or JSON by [`write_reconciliation`].
+//!
+//! For lists too big to hold in memory, `--stream` uses [`solve_streaming`], which reads the input a line at a time
+//! with [`parse_line`], sorts each list with an [`ExternalSorter`], and computes both parts from the merged runs.
 
+use crate::helpers::external_sort::{ExternalSorter, MergedRuns, SortedRuns};
+use crate::report::{OutputFormat, PartResult, Status};
+use crate::runner::{Param, Runner};
 use itertools::Itertools;
-use std::fs;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap};
+use std::io::{self, BufRead, Write};
+use std::iter::Peekable;
+use std::num::ParseIntError;
+use std::str::FromStr;
+use std::time::Instant;
+
+/// How many ids from each list [`solve_streaming`] holds in memory before spilling them to disk, i.e. 4MiB per list
+pub const STREAM_CHUNK_SIZE: usize = 1 << 20;
+
+/// The values that can be passed to [`run`]
+pub const PARAMS: [Param; 1] = [Param {
+    name: "pairing",
+    description: "How to pair the lists for part 1, `sorted` or `matching` when their lengths differ (default sorted)",
+}];
+
+/// How the two lists are paired up for part 1
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+enum Pairing {
+    /// Zip the sorted lists with [`to_sorted_pairs`], dropping any extra ids in the longer list
+    Sorted,
+    /// Use [`min_distance_matching`], leaving the ids it can't pair unmatched
+    Matching,
+}
+
+impl FromStr for Pairing {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sorted" => Ok(Pairing::Sorted),
+            "matching" => Ok(Pairing::Matching),
+            _ => Err(format!("Unknown pairing {s}")),
+        }
+    }
+}
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-1-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 1.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-1-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let (left, right) = parse_input(&contents);
+    let pairing = runner.param("pairing", Pairing::Sorted);
 
-    println!(
-        "Sum of distances: {}",
-        sum_diffs(&to_sorted_pairs(&left, &right))
-    );
-    println!(
-        "Sum of similarity scores: {}",
+    runner.part(1, "Sum of distances: {}", || match pairing {
+        Pairing::Sorted => sum_diffs(&to_sorted_pairs(&left, &right)),
+        Pairing::Matching => sum_diffs(&min_distance_matching(&left, &right).pairs),
+    });
+    runner.part(2, "Sum of similarity scores: {}", || {
         sum_similarity_scores(&left, &right)
-    );
+    });
 }
 
 /// Build up lists of ids from the puzzle input. The input is two columns of numbers separated by three spaces, e.g.
@@ -35,21 +85,35 @@ pub fn run() {
 /// 3   9
 /// 3   3
 /// ```
+///
+/// If the lists have different lengths, the lines past the end of the shorter list only have a value on one side of
+/// the separator, e.g. `   7` for an extra id in the right list. Lines that can't be parsed are skipped.
 fn parse_input(input: &String) -> (Vec<u32>, Vec<u32>) {
     let mut left = vec![];
     let mut right = vec![];
 
     input
         .lines()
-        .flat_map(|line| line.split_once("   "))
+        .flat_map(|line| parse_line(line).ok())
         .for_each(|(l, r)| {
-            left.push(l.parse::<u32>().unwrap());
-            right.push(r.parse::<u32>().unwrap());
+            left.extend(l);
+            right.extend(r);
         });
 
     (left, right)
 }
 
+/// Parse one line of the input into the ids it has for the left and right lists, either of which may be missing
+fn parse_line(line: &str) -> Result<(Option<u32>, Option<u32>), ParseIntError> {
+    let (l, r) = line.split_once("   ").unwrap_or((line, ""));
+    let parse = |id: &str| match id.trim() {
+        "" => Ok(None),
+        id => id.parse::<u32>().map(Some),
+    };
+
+    Ok((parse(l)?, parse(r)?))
+}
+
 /// The first part of the solution to part 1. Pair the lowest integers in each list, then second lowest, and so on...
 fn to_sorted_pairs(left: &Vec<u32>, right: &Vec<u32>) -> Vec<(u32, u32)> {
     let sorted_left = left.iter().cloned().sorted();
@@ -62,6 +126,107 @@ fn sum_diffs(pairs: &Vec<(u32, u32)>) -> u32 {
     pairs.iter().map(|&(l, r)| l.abs_diff(r)).sum()
 }
 
+/// The result of [`min_distance_matching`]
+#[derive(Eq, PartialEq, Debug, Clone)]
+struct Matching {
+    /// The matched `(left, right)` ids, in ascending order
+    pairs: Vec<(u32, u32)>,
+    /// The ids left over from the longer list, in ascending order. At most one of these is non-empty.
+    unmatched_left: Vec<u32>,
+    unmatched_right: Vec<u32>,
+}
+
+/// Pair every id in the shorter list with a different id in the longer list, so that the sum of the distances is as
+/// small as possible, and list the ids in the longer list that weren't needed.
+///
+/// This is a min-cost flow along the number line, simulated with two heaps in the style of the "mice and holes"
+/// problem. Working through all the ids in order, each id from the shorter list must take a partner, so it takes the
+/// cheapest option offered by the earlier ids in the longer list, or a stand-in with a huge cost if there are none.
+/// Each choice pushes an offer to undo it, so that a later id from the longer list can take over the pairing if that
+/// is cheaper, which in turn offers its id back to later ids in the shorter list. Every offer runs from some earlier
+/// id to the current one, so rather than tracking who is paired with whom, the net flow across each gap between
+/// neighbouring ids is kept in a difference array. An id from the longer list is matched when one unit flows into it,
+/// and as some best matching never crosses over, the matched ids zipped with the shorter list give the pairs.
+///
+/// That is `O((n + m) log(n + m))` time and `O(n + m)` memory for lists of length `n` and `m`. When the lengths are
+/// equal every id is matched, and this is the same as [`to_sorted_pairs`].
+fn min_distance_matching(left: &Vec<u32>, right: &Vec<u32>) -> Matching {
+    /// The cost of pairing an id with the stand-in. It is always undone, as the longer list has enough ids.
+    const STAND_IN: i64 = 1 << 40;
+
+    let swapped = left.len() > right.len();
+    let (short, long) = if swapped {
+        (right, left)
+    } else {
+        (left, right)
+    };
+    let ids: Vec<(u32, bool)> = short
+        .iter()
+        .map(|&id| (id, false))
+        .chain(long.iter().map(|&id| (id, true)))
+        .sorted()
+        .collect();
+
+    // Offers as `(key, start)`, where taking one at `x` changes the total distance by `key + x`, and moves one unit
+    // of flow between the ids at index `start` and the current index
+    let mut short_offers: BinaryHeap<Reverse<(i64, usize)>> = BinaryHeap::new();
+    let mut long_offers: BinaryHeap<Reverse<(i64, usize)>> = BinaryHeap::new();
+    // The change in rightward flow from the gap after each index onwards
+    let mut flow = vec![0i64; ids.len() + 1];
+
+    for (idx, &(id, from_long)) in ids.iter().enumerate() {
+        let x = id as i64;
+        if from_long {
+            match short_offers.peek() {
+                Some(&Reverse((key, start))) if key + x < 0 => {
+                    short_offers.pop();
+                    flow[start] += 1;
+                    flow[idx] -= 1;
+                    long_offers.push(Reverse((-key - 2 * x, start)));
+                }
+                _ => long_offers.push(Reverse((-x, idx))),
+            }
+        } else {
+            let (key, start) = long_offers
+                .pop()
+                .map_or((STAND_IN - x, 0), |Reverse(offer)| offer);
+            flow[start] -= 1;
+            flow[idx] += 1;
+            short_offers.push(Reverse((-key - 2 * x, start)));
+        }
+    }
+
+    let mut matched = Vec::with_capacity(short.len());
+    let mut unmatched = Vec::with_capacity(long.len() - short.len());
+    let mut flow_in = 0;
+    for (&(id, from_long), change) in ids.iter().zip(flow) {
+        let flow_out = flow_in + change;
+        if from_long {
+            if flow_in - flow_out == 1 {
+                matched.push(id);
+            } else {
+                unmatched.push(id);
+            }
+        }
+        flow_in = flow_out;
+    }
+
+    let short = short.iter().copied().sorted();
+    if swapped {
+        Matching {
+            pairs: matched.into_iter().zip(short).collect(),
+            unmatched_left: unmatched,
+            unmatched_right: Vec::new(),
+        }
+    } else {
+        Matching {
+            pairs: short.zip(matched).collect(),
+            unmatched_left: Vec::new(),
+            unmatched_right: unmatched,
+        }
+    }
+}
+
 /// The solution to part 2. The similarity score for a number in the left-hand column is that number multiplied by the
 /// number of times it appears in the right-hand column.
 fn sum_similarity_scores(left: &Vec<u32>, right: &Vec<u32>) -> usize {
@@ -71,10 +236,350 @@ fn sum_similarity_scores(left: &Vec<u32>, right: &Vec<u32>) -> usize {
         .sum()
 }
 
+/// The same answers as parts 1 and 2, computed without holding the lists in memory. The lists are read from `reader` a
+/// line at a time and sorted with an [`ExternalSorter`] holding at most `chunk_size` ids from each in memory. Part 1
+/// zips the merged lists like [`to_sorted_pairs`], and part 2 walks them together, multiplying the counts of each id
+/// found in both, rather than looking the counts up like [`sum_similarity_scores`]. The sums are `u64`, as they can
+/// outgrow the in-memory solutions' types on lists this large.
+///
+/// The answers are printed as they are found for [`OutputFormat::Text`], like [`Runner::part`] does, and returned
+/// for [`crate::report::write_report`]. Part 1's duration includes reading and sorting the lists.
+pub fn solve_streaming(
+    reader: impl BufRead,
+    chunk_size: usize,
+    format: OutputFormat,
+) -> io::Result<Vec<PartResult>> {
+    let mut start = Instant::now();
+    let (left, right) = sort_streamed(reader, chunk_size)?;
+
+    let parts: [(u8, &str, StreamedPart); 2] = [
+        (1, "Sum of distances: {}", streamed_distance),
+        (2, "Sum of similarity scores: {}", streamed_similarity),
+    ];
+    let mut results = Vec::with_capacity(parts.len());
+    for (part, label, solve) in parts {
+        let answer = solve(&left, &right)?.to_string();
+        if format == OutputFormat::Text {
+            println!("{}", label.replacen("{}", &answer, 1));
+        }
+
+        results.push(PartResult {
+            day: 1,
+            part,
+            answer,
+            duration: start.elapsed(),
+            status: Status::Solved,
+            cache_key: None,
+        });
+        start = Instant::now();
+    }
+
+    Ok(results)
+}
+
+/// One part of [`solve_streaming`], computed from the sorted lists
+type StreamedPart = fn(&SortedRuns, &SortedRuns) -> io::Result<u64>;
+
+/// Read the lists from `reader` a line at a time into an [`ExternalSorter`] each
+fn sort_streamed(reader: impl BufRead, chunk_size: usize) -> io::Result<(SortedRuns, SortedRuns)> {
+    let mut left = ExternalSorter::new(chunk_size)?;
+    let mut right = ExternalSorter::new(chunk_size)?;
+
+    for line in reader.lines() {
+        let (l, r) =
+            parse_line(&line?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if let Some(l) = l {
+            left.push(l)?;
+        }
+        if let Some(r) = r {
+            right.push(r)?;
+        }
+    }
+
+    Ok((left.finish()?, right.finish()?))
+}
+
+/// Sum the distances between the sorted lists, as [`sum_diffs`] does with [`to_sorted_pairs`]
+fn streamed_distance(left: &SortedRuns, right: &SortedRuns) -> io::Result<u64> {
+    let mut total = 0;
+    for (l, r) in left.iter()?.zip(right.iter()?) {
+        total += l?.abs_diff(r?) as u64;
+    }
+
+    Ok(total)
+}
+
+/// Sum the similarity scores from sorted lists, by counting each run of equal ids on both sides, as an id that
+/// appears `a` times on the left and `b` times on the right scores `id * b`, `a` times over.
+fn streamed_similarity(left: &SortedRuns, right: &SortedRuns) -> io::Result<u64> {
+    let (mut left, mut right) = (left.iter()?.peekable(), right.iter()?.peekable());
+    let (mut l, mut r) = (next_id_count(&mut left)?, next_id_count(&mut right)?);
+
+    let mut total = 0;
+    while let (Some((left_id, left_count)), Some((right_id, right_count))) = (l, r) {
+        if left_id == right_id {
+            total += left_id as u64 * left_count * right_count;
+        }
+        if left_id <= right_id {
+            l = next_id_count(&mut left)?;
+        }
+        if right_id <= left_id {
+            r = next_id_count(&mut right)?;
+        }
+    }
+
+    Ok(total)
+}
+
+/// Take the next id from a sorted list along with how many times it is repeated, or `None` at the end of the list
+fn next_id_count(ids: &mut Peekable<MergedRuns>) -> io::Result<Option<(u32, u64)>> {
+    let Some(id) = ids.next().transpose()? else {
+        return Ok(None);
+    };
+
+    let mut count = 1;
+    while ids
+        .next_if(|next| matches!(next, Ok(next) if *next == id))
+        .is_some()
+    {
+        count += 1;
+    }
+
+    Ok(Some((id, count)))
+}
+
+/// The kind of disagreement a [`ReconciliationRow`] describes
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Discrepancy {
+    /// The id appears in the left list but not the right
+    OnlyLeft,
+    /// The id appears in the right list but not the left
+    OnlyRight,
+    /// The id appears in both lists, but a different number of times
+    CountDiffers,
+    /// A pair from [`min_distance_matching`], and the distance between them
+    Pair,
+    /// An id from the longer list that [`min_distance_matching`] left without a partner
+    Unmatched,
+}
+
+/// A row of the reconciliation report. Rows about an id fill in `id` and the counts, rows about a pair fill in `rank`,
+/// `left`, `right` and `distance`, and unmatched rows fill in the side the id came from, so that all the rows fit in
+/// one CSV file.
+#[derive(Eq, PartialEq, Debug, Clone, Serialize)]
+pub struct ReconciliationRow {
+    pub kind: Discrepancy,
+    pub id: Option<u32>,
+    pub left_count: Option<usize>,
+    pub right_count: Option<usize>,
+    pub rank: Option<usize>,
+    pub left: Option<u32>,
+    pub right: Option<u32>,
+    pub distance: Option<u32>,
+}
+
+impl ReconciliationRow {
+    fn for_id(kind: Discrepancy, id: u32, left_count: usize, right_count: usize) -> Self {
+        ReconciliationRow {
+            kind,
+            id: Some(id),
+            left_count: Some(left_count),
+            right_count: Some(right_count),
+            rank: None,
+            left: None,
+            right: None,
+            distance: None,
+        }
+    }
+
+    fn for_pair(rank: usize, (left, right): (u32, u32)) -> Self {
+        ReconciliationRow {
+            kind: Discrepancy::Pair,
+            id: None,
+            left_count: None,
+            right_count: None,
+            rank: Some(rank),
+            left: Some(left),
+            right: Some(right),
+            distance: Some(left.abs_diff(right)),
+        }
+    }
+
+    fn for_unmatched(left: Option<u32>, right: Option<u32>) -> Self {
+        ReconciliationRow {
+            kind: Discrepancy::Unmatched,
+            id: None,
+            left_count: None,
+            right_count: None,
+            rank: None,
+            left,
+            right,
+            distance: None,
+        }
+    }
+}
+
+/// List where the two lists disagree: ids only in one list, then ids in both with different counts, each in id
+/// order, followed by every pair from [`min_distance_matching`] with its distance, ranked from 1, and then the ids it
+/// left unmatched. When the lists are the same length the pairs are the same as [`to_sorted_pairs`].
+fn reconcile(left: &Vec<u32>, right: &Vec<u32>) -> Vec<ReconciliationRow> {
+    let left_counts = left.iter().counts();
+    let right_counts = right.iter().counts();
+    let ids: BTreeSet<&u32> = left_counts
+        .keys()
+        .chain(right_counts.keys())
+        .copied()
+        .collect();
+
+    let counts: Vec<(u32, usize, usize)> = ids
+        .into_iter()
+        .map(|id| {
+            let left_count = left_counts.get(id).copied().unwrap_or(0);
+            let right_count = right_counts.get(id).copied().unwrap_or(0);
+            (*id, left_count, right_count)
+        })
+        .collect();
+
+    let id_rows = |kind: Discrepancy, include: fn(usize, usize) -> bool| {
+        counts
+            .iter()
+            .filter(move |&&(_, l, r)| include(l, r))
+            .map(move |&(id, l, r)| ReconciliationRow::for_id(kind, id, l, r))
+    };
+    let matching = min_distance_matching(left, right);
+
+    id_rows(Discrepancy::OnlyLeft, |l, r| l > 0 && r == 0)
+        .chain(id_rows(Discrepancy::OnlyRight, |l, r| l == 0 && r > 0))
+        .chain(id_rows(Discrepancy::CountDiffers, |l, r| {
+            l > 0 && r > 0 && l != r
+        }))
+        .chain(
+            matching
+                .pairs
+                .into_iter()
+                .enumerate()
+                .map(|(i, pair)| ReconciliationRow::for_pair(i + 1, pair)),
+        )
+        .chain(
+            matching
+                .unmatched_left
+                .into_iter()
+                .map(|id| ReconciliationRow::for_unmatched(Some(id), None)),
+        )
+        .chain(
+            matching
+                .unmatched_right
+                .into_iter()
+                .map(|id| ReconciliationRow::for_unmatched(None, Some(id))),
+        )
+        .collect()
+}
+
+/// Format the rows as a plain text table with aligned columns, showing `-` for values that don't apply to a row
+fn reconciliation_table(rows: &[ReconciliationRow]) -> String {
+    fn cell<T: ToString>(value: Option<T>) -> String {
+        value.map_or("-".to_string(), |v| v.to_string())
+    }
+
+    let headings = [
+        "kind",
+        "id",
+        "left count",
+        "right count",
+        "rank",
+        "left",
+        "right",
+        "distance",
+    ];
+    let cells: Vec<[String; 8]> = rows
+        .iter()
+        .map(|row| {
+            [
+                serde_json::to_value(row.kind)
+                    .unwrap()
+                    .as_str()
+                    .unwrap()
+                    .to_string(),
+                cell(row.id),
+                cell(row.left_count),
+                cell(row.right_count),
+                cell(row.rank),
+                cell(row.left),
+                cell(row.right),
+                cell(row.distance),
+            ]
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..headings.len())
+        .map(|i| {
+            cells
+                .iter()
+                .map(|row| row[i].len())
+                .chain([headings[i].len()])
+                .max()
+                .unwrap()
+        })
+        .collect();
+
+    let format_row = |values: Vec<&str>| {
+        values
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (value, &width))| {
+                if i == 0 {
+                    format!("{value:<width$}")
+                } else {
+                    format!("{value:>width$}")
+                }
+            })
+            .join("  ")
+    };
+
+    [format_row(headings.to_vec())]
+        .into_iter()
+        .chain(
+            cells
+                .iter()
+                .map(|row| format_row(row.iter().map(String::as_str).collect())),
+        )
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// Write the [`reconcile`] report for the puzzle input, as a table for [`OutputFormat::Text`]
+pub fn write_reconciliation(
+    input: &String,
+    format: OutputFormat,
+    mut writer: impl Write,
+) -> std::io::Result<()> {
+    let (left, right) = parse_input(input);
+    let rows = reconcile(&left, &right);
+
+    match format {
+        OutputFormat::Text => write!(writer, "{}", reconciliation_table(&rows)),
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, &rows)?;
+            writeln!(writer)
+        }
+        OutputFormat::Csv => {
+            let mut csv = csv::Writer::from_writer(writer);
+            for row in &rows {
+                csv.serialize(row)?;
+            }
+            csv.flush()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day_1::*;
-    
+    use itertools::EitherOrBoth;
+    use proptest::prelude::*;
+
     fn sample_input() -> String {
         "3   4
 4   3
@@ -108,6 +613,256 @@ mod tests {
         );
     }
 
+    #[test]
+    fn can_parse_lists_of_different_lengths() {
+        assert_eq!(
+            parse_input(&"3   4\n4   3\n2\n   9\n1   \n".to_string()),
+            (vec![3, 4, 2, 1], vec![4, 3, 9])
+        );
+    }
+
+    #[test]
+    fn skips_lines_that_cannot_be_parsed() {
+        assert_eq!(
+            parse_input(&"3   4\n4 x\n\n2   5\n\n".to_string()),
+            (vec![3, 2], vec![4, 5])
+        );
+    }
+
+    #[test]
+    fn can_parse_pairing() {
+        assert_eq!("sorted".parse(), Ok(Pairing::Sorted));
+        assert_eq!("matching".parse(), Ok(Pairing::Matching));
+        assert!("zip".parse::<Pairing>().is_err());
+    }
+
+    #[test]
+    fn can_match_lists_of_equal_length() {
+        let (left, right) = (vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3]);
+        let matching = min_distance_matching(&left, &right);
+
+        assert_eq!(matching.pairs, to_sorted_pairs(&left, &right));
+        assert!(matching.unmatched_left.is_empty());
+        assert!(matching.unmatched_right.is_empty());
+    }
+
+    #[test]
+    fn can_match_lists_of_different_lengths() {
+        assert_eq!(
+            min_distance_matching(&vec![10, 20], &vec![1, 11, 19, 30, 40]),
+            Matching {
+                pairs: vec![(10, 11), (20, 19)],
+                unmatched_left: vec![],
+                unmatched_right: vec![1, 30, 40],
+            }
+        );
+        assert_eq!(
+            min_distance_matching(&vec![5, 100, 8], &vec![6]),
+            Matching {
+                pairs: vec![(5, 6)],
+                unmatched_left: vec![8, 100],
+                unmatched_right: vec![],
+            }
+        );
+        assert_eq!(
+            min_distance_matching(&vec![], &vec![2, 1]),
+            Matching {
+                pairs: vec![],
+                unmatched_left: vec![],
+                unmatched_right: vec![1, 2],
+            }
+        );
+    }
+
+    /// Try every way of pairing the shorter list with distinct ids from the longer list
+    fn brute_force_distance(left: &[u32], right: &[u32]) -> u32 {
+        let (short, long) = if left.len() <= right.len() {
+            (left, right)
+        } else {
+            (right, left)
+        };
+
+        long.iter()
+            .permutations(short.len())
+            .map(|partners| {
+                short
+                    .iter()
+                    .zip(partners)
+                    .map(|(a, b)| a.abs_diff(*b))
+                    .sum()
+            })
+            .min()
+            .unwrap()
+    }
+
+    proptest! {
+        #[test]
+        fn matching_is_as_short_as_brute_force(
+            left in prop::collection::vec(0u32..50, 0..6),
+            right in prop::collection::vec(0u32..50, 0..6),
+        ) {
+            let matching = min_distance_matching(&left, &right);
+
+            prop_assert_eq!(matching.pairs.len(), left.len().min(right.len()));
+            let used_left = matching.pairs.iter().map(|&(l, _)| l).chain(matching.unmatched_left.clone());
+            let used_right = matching.pairs.iter().map(|&(_, r)| r).chain(matching.unmatched_right.clone());
+            prop_assert_eq!(used_left.sorted().collect_vec(), left.iter().copied().sorted().collect_vec());
+            prop_assert_eq!(used_right.sorted().collect_vec(), right.iter().copied().sorted().collect_vec());
+            prop_assert_eq!(sum_diffs(&matching.pairs), brute_force_distance(&left, &right));
+        }
+    }
+
+    #[test]
+    fn can_match_large_lists_quickly() {
+        let left: Vec<u32> = (0..30_000).map(|i| i * 7 % 100_003).collect();
+        let right: Vec<u32> = (0..70_000).map(|i| i * 13 % 100_019).collect();
+        let matching = min_distance_matching(&left, &right);
+
+        assert_eq!(matching.pairs.len(), 30_000);
+        assert_eq!(matching.unmatched_right.len(), 40_000);
+
+        let matching = min_distance_matching(&right, &left);
+        assert_eq!(matching.pairs.len(), 30_000);
+        assert_eq!(matching.unmatched_left.len(), 40_000);
+    }
+
+    fn streamed(input: &str, chunk_size: usize) -> (u64, u64) {
+        let results = solve_streaming(input.as_bytes(), chunk_size, OutputFormat::Json).unwrap();
+        assert_eq!(
+            results.iter().map(|result| result.part).collect_vec(),
+            vec![1, 2]
+        );
+
+        let answer = |idx: usize| results[idx].answer.parse().unwrap();
+        (answer(0), answer(1))
+    }
+
+    #[test]
+    fn can_solve_streaming() {
+        assert_eq!(streamed(&sample_input(), 2), (11, 31));
+        assert_eq!(streamed(&sample_input(), STREAM_CHUNK_SIZE), (11, 31));
+        assert_eq!(streamed("", 2), (0, 0));
+        assert!(solve_streaming("1   x".as_bytes(), 2, OutputFormat::Json).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn streaming_matches_in_memory(
+            left in prop::collection::vec(0u32..20, 0..50),
+            right in prop::collection::vec(0u32..20, 0..50),
+            chunk_size in 1usize..10,
+        ) {
+            let input = left
+                .iter()
+                .map(|id| id.to_string())
+                .zip_longest(right.iter().map(|id| id.to_string()))
+                .map(|ids| match ids {
+                    EitherOrBoth::Both(l, r) => format!("{l}   {r}"),
+                    EitherOrBoth::Left(l) => l,
+                    EitherOrBoth::Right(r) => format!("   {r}"),
+                })
+                .join("\n");
+
+            prop_assert_eq!(
+                streamed(&input, chunk_size),
+                (
+                    sum_diffs(&to_sorted_pairs(&left, &right)) as u64,
+                    sum_similarity_scores(&left, &right) as u64
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn can_reconcile_lists() {
+        let rows = reconcile(&vec![3, 4, 2, 1, 3, 3], &vec![4, 3, 5, 3, 9, 3]);
+        let id_rows: Vec<&ReconciliationRow> = rows
+            .iter()
+            .filter(|row| row.kind != Discrepancy::Pair)
+            .collect();
+
+        assert_eq!(
+            id_rows,
+            vec![
+                &ReconciliationRow::for_id(Discrepancy::OnlyLeft, 1, 1, 0),
+                &ReconciliationRow::for_id(Discrepancy::OnlyLeft, 2, 1, 0),
+                &ReconciliationRow::for_id(Discrepancy::OnlyRight, 5, 0, 1),
+                &ReconciliationRow::for_id(Discrepancy::OnlyRight, 9, 0, 1),
+            ]
+        );
+
+        let pairs: Vec<(Option<usize>, Option<u32>)> = rows
+            .iter()
+            .filter(|row| row.kind == Discrepancy::Pair)
+            .map(|row| (row.rank, row.distance))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (Some(1), Some(2)),
+                (Some(2), Some(1)),
+                (Some(3), Some(0)),
+                (Some(4), Some(1)),
+                (Some(5), Some(2)),
+                (Some(6), Some(5)),
+            ]
+        );
+
+        let rows = reconcile(&vec![7, 7, 8], &vec![7, 8, 8]);
+        assert_eq!(
+            rows[..2],
+            [
+                ReconciliationRow::for_id(Discrepancy::CountDiffers, 7, 2, 1),
+                ReconciliationRow::for_id(Discrepancy::CountDiffers, 8, 1, 2),
+            ]
+        );
+    }
+
+    fn reconciliation_string(format: OutputFormat) -> String {
+        let mut buffer = Vec::new();
+        write_reconciliation(&"1   1\n2   1\n".to_string(), format, &mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn can_write_reconciliation_table() {
+        assert_eq!(
+            reconciliation_string(OutputFormat::Text),
+            "kind           id  left count  right count  rank  left  right  distance
+only_left       2           1            0     -     -      -         -
+count_differs   1           1            2     -     -      -         -
+pair            -           -            -     1     1      1         0
+pair            -           -            -     2     2      1         1
+"
+        );
+    }
+
+    #[test]
+    fn can_reconcile_unmatched_ids() {
+        let rows = reconcile(&vec![1, 9], &vec![2]);
+
+        assert_eq!(
+            rows[rows.len() - 2..],
+            [
+                ReconciliationRow::for_pair(1, (1, 2)),
+                ReconciliationRow::for_unmatched(Some(9), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn can_write_reconciliation_csv() {
+        assert_eq!(
+            reconciliation_string(OutputFormat::Csv),
+            "kind,id,left_count,right_count,rank,left,right,distance
+only_left,2,1,0,,,,
+count_differs,1,1,2,,,,
+pair,,,,1,1,1,0
+pair,,,,2,2,1,1
+"
+        );
+    }
+
     #[test]
     fn can_sum_similarity_scores() {
         assert_eq!(
@@ -8,22 +8,23 @@
 //! [`TopographicalMap::get_peaks`] to recursively walk the trail permutations and get a list of peaks that terminate
 //! them. The score (part 1) gets the unique peaks before counting them, the rating counts the duplicates.
 
+use crate::runner::Runner;
 use itertools::Itertools;
-use std::fs;
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-10-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 10.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-10-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let topographical_map = parse_input(&contents);
 
-    println!("The trailhead score is {}", topographical_map.total_score());
-    println!(
-        "The trailhead rating is {}",
+    runner.part(1, "The trailhead score is {}", || {
+        topographical_map.total_score()
+    });
+    runner.part(2, "The trailhead rating is {}", || {
         topographical_map.total_rating()
-    );
+    });
 }
 
 type Coordinate = (usize, usize);
@@ -132,7 +133,7 @@ fn parse_input(input: &String) -> TopographicalMap {
 #[cfg(test)]
 mod tests {
     use crate::day_10::*;
-    
+
     fn small_example() -> TopographicalMap {
         TopographicalMap {
             cells: vec![
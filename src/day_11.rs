@@ -5,26 +5,24 @@
 //! [`count_after_blinks`] solves both parts, calling [`count_for_stone`] recursively. This is cached as there are a
 //! lot of repeat small numbers at each depth. [`blink`] handles a single blink.
 
+use crate::runner::Runner;
 use cached::proc_macro::cached;
-use std::fs;
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-11-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 11.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-11-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let stones = parse_input(&contents);
 
-    println!(
-        "After 25 blinks there are {} stones",
+    runner.part(1, "After 25 blinks there are {} stones", || {
         count_after_blinks(&stones, 25)
-    );
+    });
 
-    println!(
-        "After 75 blinks there are {} stones",
+    runner.part(2, "After 75 blinks there are {} stones", || {
         count_after_blinks(&stones, 75)
-    );
+    });
 }
 
 /// Turn the space separated number strings into `u64`s
@@ -85,7 +83,7 @@ fn count_after_blinks(stones: &Vec<u64>, number_of_blinks: u8) -> usize {
 #[cfg(test)]
 mod tests {
     use crate::day_11::*;
-    
+
     #[test]
     fn can_parse_input() {
         assert_eq!(
@@ -5,24 +5,29 @@
 //! [`Garden::find_regions`] splits the Garden into [`Region`]s. [`Garden::total_fencing_cost`] solves part 1 using
 //! the data collected when finding the regions. [`Garden::total_fencing_cost_with_discount`] solves part 2, using
 //! [`Region::count_edges`] to find the unique edges in a region by counting corners in the perimeter.
+//!
+//! [`figures`] draws the regions of the larger example for the write-up.
 
+use crate::figures::{cell, cell_centre, palette, Figure, Svg, CELL_SIZE};
+use crate::runner::Runner;
 use itertools::Itertools;
 use std::collections::HashSet;
-use std::{fs, usize};
+use std::usize;
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-12-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 12.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-12-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let garden = parse_input(&contents);
 
-    println!("The total fencing cost is {}", garden.total_fencing_cost());
-    println!(
-        "The total discounted fencing cost is {}",
+    runner.part(1, "The total fencing cost is {}", || {
+        garden.total_fencing_cost()
+    });
+    runner.part(2, "The total discounted fencing cost is {}", || {
         garden.total_fencing_cost_with_discount()
-    );
+    });
 }
 
 /// Coordinates of a plot within a [`Garden`]
@@ -251,6 +256,59 @@ impl Garden {
     }
 }
 
+/// The larger example garden from the puzzle description, used for [`figures`]
+//noinspection SpellCheckingInspection
+const LARGER_EXAMPLE: &str = "RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+";
+
+/// Draw the regions of the larger example garden, each plot shaded by region and labelled with its crop, and the
+/// region's perimeter outlined as found by [`Garden::walk_region`].
+pub fn figures() -> Vec<Figure> {
+    let garden = parse_input(&LARGER_EXAMPLE.to_string());
+    let regions = garden.find_regions();
+    let mut svg = Svg::for_grid(garden.plots.len(), garden.plots[0].len());
+
+    for (i, region) in regions.iter().enumerate() {
+        let colour = palette(i, regions.len());
+        for &plot in region.plots.iter().sorted() {
+            svg.rect(cell(plot), (CELL_SIZE, CELL_SIZE), &colour);
+            svg.text(cell_centre(plot), &region.crop.to_string(), "#333");
+        }
+    }
+
+    for region in &regions {
+        for &(plot, side) in region
+            .perimeter
+            .iter()
+            .sorted_by_key(|&&(plot, side)| (plot, side as u8))
+        {
+            let (x, y) = cell(plot);
+            let (start, end) = match side {
+                Side::TOP => ((x, y), (x + CELL_SIZE, y)),
+                Side::RIGHT => ((x + CELL_SIZE, y), (x + CELL_SIZE, y + CELL_SIZE)),
+                Side::BOTTOM => ((x, y + CELL_SIZE), (x + CELL_SIZE, y + CELL_SIZE)),
+                Side::LEFT => ((x, y), (x, y + CELL_SIZE)),
+            };
+            svg.line(start, end, "#000", 2.0);
+        }
+    }
+
+    vec![Figure {
+        day: 12,
+        name: "regions",
+        svg: svg.render(),
+    }]
+}
+
 /// Parse a text grid into a [`Garden`]
 fn parse_input(input: &String) -> Garden {
     Garden {
@@ -262,7 +320,7 @@ fn parse_input(input: &String) -> Garden {
 mod tests {
     use crate::day_12::*;
     use crate::helpers::test::assert_contains_in_any_order;
-    
+
     fn example_garden() -> Garden {
         Garden {
             plots: vec![
@@ -288,20 +346,7 @@ OOOOO"
 
     //noinspection SpellCheckingInspection
     fn larger_example() -> Garden {
-        parse_input(
-            &"RRRRIICCFF
-RRRRIICCCF
-VVRRRCCFFF
-VVRCCCJFFF
-VVVVCJJCFE
-VVIVCCJJEE
-VVIIICJJEE
-MIIIIIJJEE
-MIIISIJEEE
-MMMISSJEEE
-"
-            .to_string(),
-        )
+        parse_input(&LARGER_EXAMPLE.to_string())
     }
 
     //noinspection SpellCheckingInspection
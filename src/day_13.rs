@@ -5,25 +5,26 @@
 //! [`sum_prize_costs`] solves both parts, taking an offset to be set to 10_000_000_000_000 for part 2. This uses
 //! [`Machine::get_cost_for_prize`], and [`Machine::get_presses`] to solve the machine's equations.
 
-use std::fs;
+use crate::helpers::number_theory::solve_linear_system;
+use crate::runner::Runner;
 use std::str::FromStr;
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-13-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 13.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-13-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let machines = parse_input(&contents);
 
-    println!(
-        "The total cost for available prizes is {}",
+    runner.part(1, "The total cost for available prizes is {}", || {
         sum_prize_costs(&machines, 0)
-    );
+    });
 
-    println!(
+    runner.part(
+        2,
         "The total cost for available prizes with offset is {}",
-        sum_prize_costs(&machines, 10_000_000_000_000)
+        || sum_prize_costs(&machines, 10_000_000_000_000),
     );
 }
 
@@ -96,23 +97,19 @@ impl FromStr for Machine {
 }
 
 impl Machine {
-    /// For a machine, solve the pair of linear equations it represents (one in `x` and one in `y`). Due to integer
-    /// division, the results may be rounded. Check they actually solve the equation, returning them if they do,
-    /// otherwise return `None` as the prize isn't reachable with a whole number of presses.
+    /// For a machine, solve the pair of linear equations it represents (one in `x` and one in `y`), returning `None`
+    /// if the prize isn't reachable with a whole number of presses, or the buttons move the claw in the same direction.
     ///
-    /// See also [Cramer's rule](https://en.wikipedia.org/wiki/Cramer%27s_rule).
+    /// See [`solve_linear_system`], which solves the equations exactly so there is no rounding to check for.
     fn get_presses(&self, offset: i64) -> Option<(i64, i64)> {
         let Machine { a, b, prize } = self;
 
-        let nb = (a.y * (prize.x + offset) - a.x * (prize.y + offset)) / (a.y * b.x - a.x * b.y);
-        let na = (prize.x + offset - b.x * nb) / a.x;
-
-        // check that a and b have not been rounded
-        if na * a.x + nb * b.x == prize.x + offset && na * a.y + nb * b.y == prize.y + offset {
-            Some((na, nb))
-        } else {
-            None
-        }
+        solve_linear_system(
+            &[vec![a.x, b.x], vec![a.y, b.y]],
+            &[prize.x + offset, prize.y + offset],
+        )
+        .ok()
+        .map(|presses| (presses[0], presses[1]))
     }
 
     /// Map the number of button presses for a prize, to its cost in tokens
@@ -140,7 +137,7 @@ fn sum_prize_costs(machines: &Vec<Machine>, offset: i64) -> i64 {
 #[cfg(test)]
 mod tests {
     use crate::day_13::*;
-    
+
     fn example_machines() -> Vec<Machine> {
         vec![
             Machine {
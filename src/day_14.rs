@@ -5,36 +5,48 @@
 //! [`total_safety_factor_after_steps`] is used to solve part 1, delegating to [`simulate_robots`] and
 //! [`total_safety_factor`] which groups robots into [`Quadrant`]s and calculates the product.
 //!
-//! [`guess_tree_seconds`] uses [`iterate_seconds`] to loop through all the possible positions of the robots, find
-//! the one with the lowest [`total_safety_factor`] as a proxy for the robots clustering into a tree.
+//! [`guess_tree_seconds`] simulates one period of the robots' rows and columns separately, then scores each combination
+//! of the two, using the [`chinese_remainder`] theorem to find which second it occurs at. The one with the lowest
+//! [`total_safety_factor`] is used as a proxy for the robots clustering into a tree.
 //! [`render_robots`] can be used to show the robot's current position visually
 
 use crate::day_14::Quadrant::*;
+use crate::helpers::number_theory::chinese_remainder;
+use crate::progress::Progress;
+use crate::runner::{Param, Runner};
 use itertools::Itertools;
 use std::collections::HashSet;
 use std::fmt::Debug;
-use std::fs;
-use std::iter::successors;
 use std::str::FromStr;
 
+/// The parameters read by [`run`], so the solution can be tried with other grid sizes, e.g. the example's 7 x 11
+pub const PARAMS: [Param; 2] = [
+    Param {
+        name: "rows",
+        description: "The height of the grid the robots move in (default 103)",
+    },
+    Param {
+        name: "columns",
+        description: "The width of the grid the robots move in (default 101)",
+    },
+];
+
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-14-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 14.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-14-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let robots = parse_input(&contents);
 
-    let bounds = (103, 101);
-    println!(
-        "The total safety factor after 100 steps is {}",
+    let bounds = (runner.param("rows", 103), runner.param("columns", 101));
+    runner.part(1, "The total safety factor after 100 steps is {}", || {
         total_safety_factor_after_steps(&robots, 100, &bounds)
-    );
+    });
 
-    println!(
-        "The tree is formed after {} seconds",
+    runner.part(2, "The tree is formed after {} seconds", || {
         guess_tree_seconds(&robots, &bounds)
-    );
+    });
 }
 
 /// A robot's position on the grid (row, column)
@@ -157,31 +169,49 @@ fn total_safety_factor_after_steps(
     total_safety_factor(&positions, bounds)
 }
 
-/// An iterator over the unique arrangements of the robots on the grid
-fn iterate_seconds<'a>(
-    robots: &'a Vec<Robot>,
-    bounds: &'a (usize, usize),
-) -> impl Iterator<Item = Vec<Robot>> + 'a {
-    let first = robots.clone();
-
-    successors(Some(robots.clone()), move |robots| {
-        let next = simulate_robots(robots, 1, bounds);
-        if next != first {
-            Some(next)
-        } else {
-            None
-        }
-    })
-}
-
-/// Guesses which second shows the image by finding which has the lowest `total_safety_factor`
-fn guess_tree_seconds(robots: &Vec<Robot>, bounds: &(usize, usize)) -> usize {
-    let (pos, _) = iterate_seconds(robots, bounds)
-        .enumerate()
-        .min_by_key(|(_, robots)| total_safety_factor(robots, bounds))
-        .unwrap();
-
-    pos
+/// Guesses which second shows the image by finding which has the lowest `total_safety_factor`.
+///
+/// Each robot's row repeats every `max_r` seconds and its column every `max_c` seconds, so rather than simulating
+/// every second until the arrangement repeats, the rows and columns are simulated separately for one period each.
+/// Every pairing of a row phase with a column phase is scored, and [`chinese_remainder`] turns the pair back into the
+/// second it happens at. Ties are broken by the earliest second. If the height and width aren't coprime, some pairs
+/// of phases never happen together, and these are skipped.
+fn guess_tree_seconds(robots: &Vec<Robot>, bounds @ &(max_r, max_c): &(usize, usize)) -> usize {
+    let rows: Vec<Vec<usize>> = (0..max_r)
+        .map(|t| {
+            robots
+                .iter()
+                .map(|robot| robot.simulate(t, bounds).position.0)
+                .collect()
+        })
+        .collect();
+    let columns: Vec<Vec<usize>> = (0..max_c)
+        .map(|t| {
+            robots
+                .iter()
+                .map(|robot| robot.simulate(t, bounds).position.1)
+                .collect()
+        })
+        .collect();
+
+    let progress = Progress::new("day 14", (max_r * max_c) as u64);
+    (0..max_r)
+        .cartesian_product(0..max_c)
+        .filter_map(|(tr, tc)| {
+            progress.add(1);
+            let (seconds, _) =
+                chinese_remainder(&[(tr as i64, max_r as i64), (tc as i64, max_c as i64)])?;
+            let positions = rows[tr]
+                .iter()
+                .zip(&columns[tc])
+                .map(|(&r, &c)| Robot::new((r, c), (0, 0)))
+                .collect();
+
+            Some((total_safety_factor(&positions, bounds), seconds as usize))
+        })
+        .min()
+        .map(|(_, seconds)| seconds)
+        .unwrap()
 }
 
 #[allow(dead_code)]
@@ -248,7 +278,7 @@ fn render_robots(robots: &Vec<Robot>, &(r_max, c_max): &(usize, usize), show_mid
 mod tests {
     use crate::day_14::*;
     use crate::helpers::test::assert_contains_in_any_order;
-    
+
     fn example_robots() -> Vec<Robot> {
         vec![
             Robot::new((4, 0), (-3, 3)),
@@ -399,4 +429,20 @@ p=6,1 v=-2,2"
     fn can_find_frame_with_lowest_safety_factor() {
         assert_eq!(guess_tree_seconds(&tree_example_robots(), &(7, 11)), 72);
     }
+
+    #[test]
+    fn can_find_frame_when_bounds_are_not_coprime() {
+        for bounds in [(6, 10), (8, 12), (9, 9)] {
+            let expected = (0..bounds.0 * bounds.1)
+                .min_by_key(|&steps| {
+                    total_safety_factor_after_steps(&tree_example_robots(), steps, &bounds)
+                })
+                .unwrap();
+
+            assert_eq!(
+                guess_tree_seconds(&tree_example_robots(), &bounds),
+                expected
+            );
+        }
+    }
 }
@@ -12,27 +12,29 @@
 //! representation.
 
 use crate::day_15::Move::{Down, Left, Right, Up};
+use crate::runner::Runner;
 use std::collections::HashSet;
-use std::fs;
 use std::str::FromStr;
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-15-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 15.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-15-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let (warehouse, moves) = parse_input(&contents);
 
-    println!(
+    runner.part(
+        1,
         "After applying the moves the sum of the GPS coordinates is {}",
-        warehouse.apply_moves(&moves).sum_gps()
+        || warehouse.apply_moves(&moves).sum_gps(),
     );
 
-    println!(
+    runner.part(
+        2,
         "After applying the moves to the doubled warehouse the sum of the GPS coordinates is {}",
-        warehouse.double().apply_moves(&moves).sum_gps()
-    )
+        || warehouse.double().apply_moves(&moves).sum_gps(),
+    );
 }
 
 /// Represents one of the move steps of the robot
@@ -342,7 +344,7 @@ fn parse_input(input: &String) -> (SingleWarehouse, Vec<Move>) {
 #[cfg(test)]
 mod tests {
     use crate::day_15::*;
-    
+
     fn small_example_warehouse() -> SingleWarehouse {
         #[rustfmt::skip]
         let walls = vec![
@@ -9,30 +9,33 @@
 //!
 //! [`Maze::count_visited_by_best_routes`] solves part 2, using similar techniques, but running until all possible
 //! best routes are found, and analysing [`Position`].`visited` lists to produce the answer.
+//!
+//! [`figures`] draws the best routes through the larger example for the write-up.
 
 use crate::day_16::Facing::*;
+use crate::figures::{cell, cell_centre, Figure, Svg, CELL_SIZE};
+use crate::runner::Runner;
 use itertools::Itertools;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap, HashSet};
-use std::{fs, u32};
+use std::u32;
+use tracing::{debug, trace};
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-16-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 16.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-16-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let maze = parse_input(&contents);
 
-    println!(
-        "The lowest scoring route scores {}",
+    runner.part(1, "The lowest scoring route scores {}", || {
         maze.lowest_scoring_route()
-    );
+    });
 
-    println!(
-        "There are {} tiles on the best routes",
+    runner.part(2, "There are {} tiles on the best routes", || {
         maze.count_visited_by_best_routes()
-    )
+    });
 }
 
 type Coordinates = (u8, u8);
@@ -116,6 +119,7 @@ impl Maze {
         heap.push(self.starting_position());
 
         while let Some(curr) = heap.pop() {
+            trace!(coordinates = ?curr.coordinates, facing = ?curr.facing, score = curr.score, "heap pop");
             if curr.coordinates == self.end {
                 return curr.score;
             }
@@ -130,9 +134,14 @@ impl Maze {
         unreachable!("Failed to find route to end");
     }
 
-    /// Solves part 2 using A* graph search that continues until all best routes are found
-    //noinspection RsDeprecation
+    /// Solves part 2 by counting the [`Maze::tiles_on_best_routes`]
     fn count_visited_by_best_routes(&self) -> u32 {
+        self.tiles_on_best_routes().len() as u32
+    }
+
+    /// Uses A* graph search that continues until all best routes are found, collecting the tiles they visit
+    //noinspection RsDeprecation
+    fn tiles_on_best_routes(&self) -> HashSet<Coordinates> {
         let mut heap: BinaryHeap<Position> = BinaryHeap::new();
         let mut visited: HashMap<(Coordinates, Facing), u32> = HashMap::new();
         let mut lowest_score = u32::MAX;
@@ -141,7 +150,9 @@ impl Maze {
         heap.push(self.starting_position());
 
         while let Some(curr) = heap.pop() {
+            trace!(coordinates = ?curr.coordinates, facing = ?curr.facing, score = curr.score, "heap pop");
             if curr.coordinates == self.end {
+                debug!(score = curr.score, lowest_score, "reached end");
                 if curr.score < lowest_score {
                     lowest_score = curr.score;
                     routes = Vec::new();
@@ -164,7 +175,7 @@ impl Maze {
             }
         }
 
-        routes.iter().flatten().unique().count() as u32
+        routes.into_iter().flatten().collect()
     }
 
     /// Turn the coordinates for the start point of the maze into the seed Position for searching the maze
@@ -306,6 +317,62 @@ impl PartialOrd for Position {
     }
 }
 
+/// The second example maze from the puzzle description, used for [`figures`]
+const LARGER_EXAMPLE: &str = "#################
+#...#...#...#..E#
+#.#.#.#.#.#.#.#.#
+#.#.#.#...#...#.#
+#.#.#.#.###.#.#.#
+#...#.#.#.....#.#
+#.#.#.#.#.#####.#
+#.#...#.#.#.....#
+#.#.#####.#.###.#
+#.#.#.......#...#
+#.#.###.#####.###
+#.#.#...#.....#.#
+#.#.#.#####.###.#
+#.#.#.........#.#
+#.#.#.#########.#
+#S#.............#
+#################";
+
+/// Draw the larger example maze with the tiles on any of the best routes highlighted
+pub fn figures() -> Vec<Figure> {
+    let maze = parse_input(&LARGER_EXAMPLE.to_string());
+    let best_routes = maze.tiles_on_best_routes();
+    let (rows, columns) = maze.bounds;
+    let mut svg = Svg::for_grid(rows as usize, columns as usize);
+
+    svg.rect(
+        (0.0, 0.0),
+        (columns as f64 * CELL_SIZE, rows as f64 * CELL_SIZE),
+        "#fff",
+    );
+    for &(r, c) in maze.hedges.iter().sorted() {
+        svg.rect(
+            cell((r as usize, c as usize)),
+            (CELL_SIZE, CELL_SIZE),
+            "#2e7d32",
+        );
+    }
+    for &(r, c) in best_routes.iter().sorted() {
+        svg.circle(
+            cell_centre((r as usize, c as usize)),
+            CELL_SIZE / 4.0,
+            "#c62828",
+        );
+    }
+    for ((r, c), label) in [(maze.start, "S"), (maze.end, "E")] {
+        svg.text(cell_centre((r as usize, c as usize)), label, "#000");
+    }
+
+    vec![Figure {
+        day: 16,
+        name: "best-routes",
+        svg: svg.render(),
+    }]
+}
+
 /// Turn the puzzle input into the internal representation.
 fn parse_input(input: &String) -> Maze {
     let mut hedges = HashSet::new();
@@ -371,26 +438,7 @@ mod tests {
     }
 
     fn larger_example_maze() -> Maze {
-        parse_input(
-            &"#################
-#...#...#...#..E#
-#.#.#.#.#.#.#.#.#
-#.#.#.#...#...#.#
-#.#.#.#.###.#.#.#
-#...#.#.#.....#.#
-#.#.#.#.#.#####.#
-#.#...#.#.#.....#
-#.#.#####.#.###.#
-#.#.#.......#...#
-#.#.###.#####.###
-#.#.#...#.....#.#
-#.#.#.#####.###.#
-#.#.#.........#.#
-#.#.#.#########.#
-#S#.............#
-#################"
-                .to_string(),
-        )
+        parse_input(&LARGER_EXAMPLE.to_string())
     }
 
     #[test]
@@ -523,5 +571,10 @@ mod tests {
     fn can_find_visited_tiles() {
         assert_eq!(example_maze().count_visited_by_best_routes(), 45);
         assert_eq!(larger_example_maze().count_visited_by_best_routes(), 64);
+
+        let tiles = example_maze().tiles_on_best_routes();
+        assert!(tiles.contains(&(13, 1)));
+        assert!(tiles.contains(&(1, 13)));
+        assert!(!tiles.contains(&(1, 1)));
     }
 }
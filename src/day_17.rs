@@ -8,26 +8,25 @@
 //! [`reverse_engineer_quine`] solves part 2 by building up the number for a from least-significant digit backwards
 //! [`brute_force_quine`] is left as deaf code for posterity
 
+use crate::progress::Progress;
+use crate::runner::Runner;
 use itertools::Itertools;
-use std::fs;
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-17-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 17.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-17-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let computer = parse_input(&contents);
 
-    println!(
-        "The output of running the program is {}",
+    runner.part(1, "The output of running the program is {}", || {
         computer.clone().run().iter().join(",")
-    );
+    });
 
-    println!(
-        "The program is a quine when register A is {}",
+    runner.part(2, "The program is a quine when register A is {}", || {
         reverse_engineer_quine(&computer)
-    );
+    });
 }
 
 /// Represents a computer and the program it will run
@@ -182,10 +181,13 @@ fn parse_input(input: &String) -> Computer {
     }
 }
 
-/// Look for a quine by trying all values of A from 0
+/// Look for a quine by trying all values of A from 0. The output has a digit for each 3-bit digit of A, so the search
+/// is reported as progress through the values with as many digits as the program.
 #[allow(dead_code)]
 fn brute_force_quine(computer: &Computer) -> usize {
+    let progress = Progress::new("day 17", 8u64.saturating_pow(computer.program.len() as u32));
     (0..)
+        .inspect(|_| progress.add(1))
         .map(|i| (i, computer.with_register_a(i).run()))
         .find(|(_, out)| *out == computer.program)
         .unwrap()
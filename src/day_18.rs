@@ -8,27 +8,30 @@
 //!
 //! Part 2 is implemented by [`MemorySpace::route_blocked_at`] which mostly re-uses part 1.
 
+use crate::runner::Runner;
 use itertools::Itertools;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap, HashSet};
-use std::fs;
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-18-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 18.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-18-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let memory_space = parse_input(&contents, 70);
 
     let position = memory_space.steps_to_goal(1024).unwrap();
-    println!(
+    runner.part(
+        1,
         "If 1024 bytes fall, the best route is {} spaces long",
-        position.travelled
+        || position.travelled,
     );
 
-    let (y, x) = memory_space.route_blocked_at(&position, 1024);
-    println!("The first blocker is {x},{y}",);
+    runner.part(2, "The first blocker is {}", || {
+        let (y, x) = memory_space.route_blocked_at(&position, 1024);
+        format!("{x},{y}")
+    });
 }
 
 /// A Coordinate in Memory Space
@@ -7,9 +7,9 @@
 //!
 //! [`PatternTreeNode::sum_combinations`] solves part one, calling [`PatternTreeNode::combinations`] for each design.
 
+use crate::runner::Runner;
 use std::cell::RefCell;
 use std::collections::HashMap;
-use std::fs;
 use std::rc::Rc;
 use Colour::*;
 
@@ -17,18 +17,18 @@ use Colour::*;
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-19-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 19.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-19-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let (pattern_tree, designs) = parse_input(&contents);
 
-    println!(
-        "{} of the designs can be made",
+    runner.part(1, "{} of the designs can be made", || {
         pattern_tree.count_matches(&designs)
-    );
+    });
 
-    println!(
+    runner.part(
+        2,
         "{} combinations of towels can be made into the designs",
-        pattern_tree.sum_combinations(&designs)
+        || pattern_tree.sum_combinations(&designs),
     );
 }
 
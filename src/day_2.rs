@@ -2,25 +2,38 @@
 //!
 //! [`parse_input`] uses [`parse_report`] to turn the input file into `Vec<Report>`. [`first_bad_level_pair`] is used
 //! by both parts to find the first pair that causes the report to be unsafe. [`analyse_reports`] solves part 1.
-//! [`report_check_with_dampener`] applies the more relaxed check for part 2, trying the permutations of dropping a
-//! level that might allow the report to pass. [`analyse_reports_with_dampener`] uses that to get the part 2 solution.
+//! [`report_check_with_dampener`] applies the more relaxed check for part 2, allowing up to the `tolerance` param's
+//! number of levels to be removed, one by default. [`analyse_reports_with_dampener`] uses that to get the part 2
+//! solution.
+//!
+//! [`levels_to_remove`] finds the fewest levels that must be removed to make a report safe, by finding the longest
+//! safe run of levels that can be kept in a single pass over the report, rather than trying each copy of the report
+//! with a level removed.
 
+use crate::runner::{Param, Runner};
 use itertools::Itertools;
-use std::fs;
+use std::collections::HashMap;
+use tracing::debug;
+
+/// The values that can be passed to [`run`]
+pub const PARAMS: [Param; 1] = [Param {
+    name: "tolerance",
+    description: "How many bad levels the Problem Dampener can remove from a report (default 1)",
+}];
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-2-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 2.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-2-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let reports = parse_input(&contents);
+    let tolerance = runner.param("tolerance", 1);
 
-    println!("There are {} safe reports", analyse_reports(&reports));
-    println!(
-        "There are {} safe reports with the dampener",
-        analyse_reports_with_dampener(&reports)
-    );
+    runner.part(1, "There are {} safe reports", || analyse_reports(&reports));
+    runner.part(2, "There are {} safe reports with the dampener", || {
+        analyse_reports_with_dampener(&reports, tolerance)
+    });
 }
 
 type Report = Vec<u32>;
@@ -58,27 +71,74 @@ fn first_bad_level_pair(report: &Report) -> Option<usize> {
     None
 }
 
-/// Returns a copy of the input report without the level at the specified index
-fn without_index(report: &Report, idx: usize) -> Report {
-    let mut new = report.clone();
-    new.remove(idx);
+/// Find the fewest levels that must be removed to make the report safe, returning their indices in ascending order.
+///
+/// The levels that are kept must be a safe report in their own right, so this finds the longest safe subsequence, once
+/// for rising levels and once for falling levels. Working through the report, the longest safe subsequence ending at a
+/// level extends the longest one ending at a level one to three steps away from it, so keeping the best so far for
+/// each level value means each level only needs three lookups, and the whole report is checked in linear time.
+fn levels_to_remove(report: &Report) -> Vec<usize> {
+    let kept = [true, false]
+        .into_iter()
+        .map(|rising| longest_safe_subsequence(report, rising))
+        .max_by_key(|kept| kept.len())
+        .unwrap();
 
-    new
+    let mut kept = kept.into_iter().peekable();
+    (0..report.len())
+        .filter(|&idx| kept.next_if_eq(&idx).is_none())
+        .collect()
 }
 
-/// If the report is unsafe, it can be considered safe enough if it becomes safe when removing one level.
-///
-/// The level to remove must be one of the pair that causes the initial check to fail, or the first level in the
-/// report if that causes the first step to be in the wrong direction.
-fn report_check_with_dampener(report: &Report) -> bool {
-    if let Some(pair_idx) = first_bad_level_pair(report) {
-        let lower_bound = if pair_idx == 1 { 0 } else { pair_idx };
-        (lower_bound..=(pair_idx + 1))
-            .into_iter()
-            .any(|level_idx| first_bad_level_pair(&without_index(report, level_idx)).is_none())
-    } else {
-        true
+/// The indices of the longest subsequence of levels that all rise, or all fall, by one to three at each step
+fn longest_safe_subsequence(report: &Report, rising: bool) -> Vec<usize> {
+    // For each level value, the length of the longest safe subsequence ending in that value, and the index it ends at
+    let mut best: HashMap<u32, (usize, usize)> = HashMap::new();
+    // The index of the previous level in the longest safe subsequence ending at each index
+    let mut previous: Vec<Option<usize>> = Vec::with_capacity(report.len());
+    let mut longest: Option<(usize, usize)> = None;
+
+    for (idx, &level) in report.iter().enumerate() {
+        let before = (1..=3)
+            .filter_map(|step| {
+                if rising {
+                    level.checked_sub(step)
+                } else {
+                    level.checked_add(step)
+                }
+            })
+            .filter_map(|value| best.get(&value))
+            .max_by_key(|&&(len, _)| len)
+            .copied();
+
+        let len = before.map_or(1, |(len, _)| len + 1);
+        previous.push(before.map(|(_, idx)| idx));
+        if best.get(&level).is_none_or(|&(other, _)| len > other) {
+            best.insert(level, (len, idx));
+        }
+        if longest.is_none_or(|(other, _)| len > other) {
+            longest = Some((len, idx));
+        }
     }
+
+    let mut kept: Vec<usize> =
+        std::iter::successors(longest.map(|(_, idx)| idx), |&idx| previous[idx]).collect();
+    kept.reverse();
+
+    kept
+}
+
+/// If the report is unsafe, it can be considered safe enough if it becomes safe when removing up to `tolerance`
+/// levels. Uses [`levels_to_remove`] to find the fewest levels that need to be removed.
+fn report_check_with_dampener(report: &Report, tolerance: usize) -> bool {
+    if first_bad_level_pair(report).is_none() {
+        return true;
+    }
+
+    let removed = levels_to_remove(report);
+    debug!(?report, ?removed, "levels to remove");
+
+    removed.len() <= tolerance
 }
 
 /// Solves part 1, counting all the reports that are safe as is
@@ -89,18 +149,19 @@ fn analyse_reports(reports: &Vec<Report>) -> usize {
         .count()
 }
 
-/// Solves part 1, counting all the reports that are safe after dampening
-fn analyse_reports_with_dampener(reports: &Vec<Report>) -> usize {
+/// Solves part 2, counting all the reports that are safe after removing at most `tolerance` levels
+fn analyse_reports_with_dampener(reports: &Vec<Report>, tolerance: usize) -> usize {
     reports
         .into_iter()
-        .filter(|&report| report_check_with_dampener(report))
+        .filter(|&report| report_check_with_dampener(report, tolerance))
         .count()
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day_2::*;
-    
+    use proptest::prelude::*;
+
     fn sample_input() -> String {
         "7 6 4 2 1
 1 2 7 8 9
@@ -142,13 +203,77 @@ mod tests {
 
     #[test]
     fn can_check_if_a_report_is_safe_with_dampener() {
-        assert_eq!(report_check_with_dampener(&vec![7, 6, 4, 2, 1]), true);
-        assert_eq!(report_check_with_dampener(&vec![1, 2, 7, 8, 9]), false);
-        assert_eq!(report_check_with_dampener(&vec![9, 7, 6, 2, 1]), false);
-        assert_eq!(report_check_with_dampener(&vec![1, 3, 2, 4, 5]), true);
-        assert_eq!(report_check_with_dampener(&vec![8, 6, 4, 4, 1]), true);
-        assert_eq!(report_check_with_dampener(&vec![1, 3, 6, 7, 9]), true);
-        assert_eq!(report_check_with_dampener(&vec![5, 3, 4, 7, 9]), true);
+        assert_eq!(report_check_with_dampener(&vec![7, 6, 4, 2, 1], 1), true);
+        assert_eq!(report_check_with_dampener(&vec![1, 2, 7, 8, 9], 1), false);
+        assert_eq!(report_check_with_dampener(&vec![9, 7, 6, 2, 1], 1), false);
+        assert_eq!(report_check_with_dampener(&vec![1, 3, 2, 4, 5], 1), true);
+        assert_eq!(report_check_with_dampener(&vec![8, 6, 4, 4, 1], 1), true);
+        assert_eq!(report_check_with_dampener(&vec![1, 3, 6, 7, 9], 1), true);
+        assert_eq!(report_check_with_dampener(&vec![5, 3, 4, 7, 9], 1), true);
+    }
+
+    #[test]
+    fn can_find_levels_to_remove() {
+        assert_eq!(levels_to_remove(&vec![7, 6, 4, 2, 1]), Vec::<usize>::new());
+        assert_eq!(levels_to_remove(&vec![1, 3, 2, 4, 5]), vec![1]);
+        assert_eq!(levels_to_remove(&vec![8, 6, 4, 4, 1]).len(), 1);
+        assert_eq!(levels_to_remove(&vec![1, 2, 7, 8, 9]).len(), 2);
+        assert_eq!(levels_to_remove(&vec![9, 7, 6, 2, 1]).len(), 2);
+        assert_eq!(
+            levels_to_remove(&vec![5, 1, 9, 6, 2, 7, 20, 8]),
+            vec![1, 2, 4, 6]
+        );
+        assert_eq!(levels_to_remove(&vec![]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn can_check_with_higher_tolerance() {
+        let report = vec![5, 1, 9, 6, 2, 7, 20, 8];
+
+        assert!(!report_check_with_dampener(&report, 3));
+        assert!(report_check_with_dampener(&report, 4));
+        assert!(!report_check_with_dampener(&report, 0));
+        assert_eq!(analyse_reports_with_dampener(&sample_reports(), 0), 2);
+        assert_eq!(analyse_reports_with_dampener(&sample_reports(), 2), 7);
+    }
+
+    #[test]
+    fn can_find_levels_to_remove_from_long_reports() {
+        let report: Report = (0..200_000)
+            .map(|i| i + (i % 5 == 0) as u32 * 1000)
+            .collect();
+
+        assert_eq!(levels_to_remove(&report).len(), 40_000);
+    }
+
+    /// The fewest levels to remove found by trying every set of levels to keep, largest first
+    fn brute_force_removals(report: &Report) -> usize {
+        (0..=report.len())
+            .rev()
+            .find(|&keep| {
+                report
+                    .iter()
+                    .copied()
+                    .combinations(keep)
+                    .any(|kept| first_bad_level_pair(&kept).is_none())
+            })
+            .map_or(0, |keep| report.len() - keep)
+    }
+
+    proptest! {
+        #[test]
+        fn removes_as_few_levels_as_brute_force(report in prop::collection::vec(0u32..12, 0..9)) {
+            let removed = levels_to_remove(&report);
+            let kept: Report = report
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| !removed.contains(idx))
+                .map(|(_, &level)| level)
+                .collect();
+
+            prop_assert_eq!(first_bad_level_pair(&kept), None);
+            prop_assert_eq!(removed.len(), brute_force_removals(&report));
+        }
     }
 
     #[test]
@@ -158,6 +283,6 @@ mod tests {
 
     #[test]
     fn can_analyse_reports_with_dampener() {
-        assert_eq!(analyse_reports_with_dampener(&sample_reports()), 5)
+        assert_eq!(analyse_reports_with_dampener(&sample_reports(), 1), 5)
     }
 }
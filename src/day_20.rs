@@ -5,27 +5,41 @@
 //! [`RaceTrack::cheats`]solves both parts. It uses [`RaceTrack::get_track_positions`] to turn the grid data into an
 //! indexed list of the spaces visited, then calculates those that match the part's criteria and counts them. There
 //! are some coordinate utilities in [`CoordinateExtensions`].
+//!
+//! [`figures`] draws the example track with its best cheats for the write-up.
 
+use crate::figures::{cell, cell_centre, Figure, Svg, CELL_SIZE};
+use crate::runner::{Param, Runner};
 use itertools::Itertools;
 use std::collections::HashSet;
-use std::fs;
+
+/// The parameters read by [`run`], so the solution can be checked against the example's smaller savings
+pub const PARAMS: [Param; 1] = [Param {
+    name: "threshold",
+    description: "The minimum picoseconds a cheat must save to be counted (default 100)",
+}];
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-20-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 20.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-20-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let track = parse_input(&contents);
+    let threshold = runner.param("threshold", 100);
 
-    println!(
-        "There are {} cheats of length 2 that save at least 100 picoseconds",
-        track.cheats(100, 2)
+    runner.part(
+        1,
+        &format!("There are {{}} cheats of length 2 that save at least {threshold} picoseconds"),
+        || track.cheats(threshold, 2),
     );
 
-    println!(
-        "There are {} cheats of length up to 20 that save at least 100 picoseconds",
-        track.cheats(100, 20)
+    runner.part(
+        2,
+        &format!(
+            "There are {{}} cheats of length up to 20 that save at least {threshold} picoseconds"
+        ),
+        || track.cheats(threshold, 20),
     );
 }
 
@@ -92,14 +106,22 @@ impl RaceTrack {
 
         visited
     }
-    /// Find the possible cheats that save at least `saving_threshold` picoseconds, and are at most `cheat_length`
+    /// Count the possible cheats that save at least `saving_threshold` picoseconds, and are at most `cheat_length`
     fn cheats(&self, saving_threshold: usize, cheat_length: usize) -> usize {
-        let track = self.get_track_positions();
+        self.find_cheats(saving_threshold, cheat_length).count()
+    }
 
-        track
-            .iter()
+    /// The possible cheats that save at least `saving_threshold` picoseconds, and are at most `cheat_length`, as the
+    /// start and end of the cheat and the picoseconds saved
+    fn find_cheats(
+        &self,
+        saving_threshold: usize,
+        cheat_length: usize,
+    ) -> impl Iterator<Item = (Coordinates, Coordinates, usize)> {
+        self.get_track_positions()
+            .into_iter()
             .tuple_combinations()
-            .flat_map(|(&(start_idx, start_coord), &(end_idx, end_coord))| {
+            .flat_map(move |((start_idx, start_coord), (end_idx, end_coord))| {
                 let manhattan_distance = start_coord.manhattan_distance(&end_coord);
                 if manhattan_distance > cheat_length {
                     None
@@ -107,12 +129,69 @@ impl RaceTrack {
                     (end_idx - start_idx)
                         .checked_sub(manhattan_distance)
                         .filter(|&distance| distance >= saving_threshold)
+                        .map(|saving| (start_coord, end_coord, saving))
                 }
             })
-            .count()
     }
 }
 
+/// The example race track from the puzzle description, used for [`figures`]
+const EXAMPLE: &str = "###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############
+";
+
+/// Draw the example track, numbering each space with how far along the track it is, and mark the cheats of length 2
+/// that save the most time as arrows from where they leave the track to where they rejoin it.
+pub fn figures() -> Vec<Figure> {
+    let track = parse_input(&EXAMPLE.to_string());
+    let rows = EXAMPLE.lines().count();
+    let columns = EXAMPLE.lines().next().unwrap().len();
+    let mut svg = Svg::for_grid(rows, columns);
+
+    svg.rect(
+        (0.0, 0.0),
+        (columns as f64 * CELL_SIZE, rows as f64 * CELL_SIZE),
+        "#555",
+    );
+    for (index, position) in track.get_track_positions() {
+        svg.rect(cell(position), (CELL_SIZE, CELL_SIZE), "#eee");
+        svg.text(cell_centre(position), &index.to_string(), "#777");
+    }
+
+    let cheats: Vec<_> = track.find_cheats(1, 2).collect();
+    let best_saving = cheats
+        .iter()
+        .map(|&(_, _, saving)| saving)
+        .max()
+        .unwrap_or(0);
+    for &(start, end, _) in cheats
+        .iter()
+        .filter(|&&(_, _, saving)| saving == best_saving)
+    {
+        svg.line(cell_centre(start), cell_centre(end), "#d81b60", 3.0);
+        svg.circle(cell_centre(end), CELL_SIZE / 5.0, "#d81b60");
+    }
+
+    vec![Figure {
+        day: 20,
+        name: "best-cheats",
+        svg: svg.render(),
+    }]
+}
+
 /// Turn the input file into the set of free spaces that make up the race's course (including start and end) as well
 /// as storing the positions of the start and end spaces.
 fn parse_input(input: &String) -> RaceTrack {
@@ -203,6 +282,11 @@ mod tests {
 
         assert_eq!(track.cheats(50, 20), 285);
         assert_eq!(track.cheats(72, 20), 29);
+
+        assert_eq!(
+            track.find_cheats(64, 2).collect::<Vec<_>>(),
+            vec![((7, 7), (7, 5), 64)]
+        );
     }
 
     #[test]
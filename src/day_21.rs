@@ -18,31 +18,31 @@
 use crate::day_21::DirectionalButton::*;
 use crate::day_21::KeyPadButton::*;
 use crate::day_21::NumericButton::*;
+use crate::runner::Runner;
 use itertools::{chain, Itertools};
 use std::cell::RefCell;
 use std::collections::HashMap;
-use std::fs;
+use std::fmt::Debug;
 use std::hash::Hash;
 use std::iter::once;
 use std::rc::Rc;
+use tracing::trace;
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-21-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 21.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-21-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let codes = parse_input(&contents);
 
-    println!(
-        "To open the first door takes {} key presses",
+    runner.part(1, "To open the first door takes {} key presses", || {
         sum_complexities(&codes, &mut keypad_chain(2))
-    );
+    });
 
-    println!(
-        "To open the second door takes {} key presses",
+    runner.part(2, "To open the second door takes {} key presses", || {
         sum_complexities(&codes, &mut keypad_chain(25))
-    );
+    });
 }
 
 /// The input buttons on pad that controls robot arm movements
@@ -183,7 +183,7 @@ struct KeyPad<T> {
 
 impl<T> KeyPad<T>
 where
-    T: Keys<T> + Copy + Clone + Eq + Hash,
+    T: Keys<T> + Copy + Clone + Eq + Hash + Debug,
 {
     /// A new [`KeyPad`] that expects a person to be pressing the keys
     fn direct_entry() -> KeyPad<T> {
@@ -246,6 +246,7 @@ where
     /// route with the shortest number of presses. The result is cached for performance.
     fn presses_for_pair(&mut self, (a, b): (KeyPadButton<T>, KeyPadButton<T>)) -> usize {
         if let Some(&result) = self.cache.get(&(a, b)) {
+            trace!(?a, ?b, result, "cache hit");
             return result;
         }
 
@@ -266,6 +267,7 @@ where
             .min()
             .expect("Failed to find safe route {a} -> {b}");
 
+        trace!(?a, ?b, count, "cache miss");
         self.cache.insert((a, b), count);
 
         count
@@ -9,25 +9,27 @@
 //! of four diffs, and then picking the maximum. For performance this packs the sequence into a 20-bit int using
 //! [`shift_diff_into_sequence_id`] to manage that.
 
+use crate::runner::Runner;
 use itertools::{iterate, Itertools};
-use std::fs;
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-22-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 22.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-22-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let seeds = parse_input(&contents);
 
-    println!(
+    runner.part(
+        1,
         "After 2000 secret numbers are generated the sum is {}",
-        iterate_and_sum(&seeds)
+        || iterate_and_sum(&seeds),
     );
 
-    println!(
+    runner.part(
+        2,
         "The best sequence for today's market buys {} bananas",
-        bananas_from_best_diff_sequence(&seeds)
+        || bananas_from_best_diff_sequence(&seeds),
     );
 }
 
@@ -1,139 +1,74 @@
 //! This is my solution for [Advent of Code - Day 23: _LAN Party_](https://adventofcode.com/2024/day/23)
 //!
-//! [`parse_input`] turns the input into a [`Network`] where each node can be mapped to the set of computer ids it's
-//! connected to.
+//! [`parse_input`] turns the input into a [`Network`], an undirected [`Graph`] of the links between computer ids.
 //!
 //! Part 1 is solved by [`Network::clusters_containing`] using [`Network::trios`]
 //!
-//! Part 2 is solved by [`Network::find_lan_password`] using [`Network::find_lan_password`]
+//! Part 2 is solved by [`Network::find_lan_password`] using [`Graph::maximum_clique`]
+//!
+//! [`figures`] draws the example network and its largest clique for the write-up.
 
+use crate::figures::{Figure, Svg};
+use crate::helpers::graph::{Graph, NodeId};
+use crate::runner::Runner;
 use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
-use std::fs;
+use std::collections::HashSet;
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-23-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 23.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-23-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let network = parse_input(&contents);
 
-    println!(
+    runner.part(
+        1,
         "There are {} trios containing ids starting with 't'",
-        network.clusters_containing("t").len()
+        || network.clusters_containing("t").len(),
     );
 
-    println!("The lan password is {}", network.find_lan_password());
+    runner.part(2, "The lan password is {}", || network.find_lan_password());
 }
 
-/// Represents a network of computers as a map from any computer to the ids of its direct connections
-#[derive(Eq, PartialEq, Debug)]
+/// Represents a network of computers as an undirected graph of the links between computer ids
+#[derive(Debug)]
 struct Network<'a> {
-    links: HashMap<&'a str, HashSet<&'a str>>,
+    links: Graph<&'a str>,
 }
 
 impl<'a> Network<'a> {
-    /// Find all the sets of three mutually interconnected computers
-    fn trios(&self) -> HashSet<Vec<&str>> {
-        let mut clusters = HashSet::new();
-
-        for (start, connected) in self.links.clone() {
-            for (a, b) in connected.iter().tuple_combinations() {
-                if self.links.get(a).unwrap().contains(b) {
-                    clusters.insert(vec![start, a, b].into_iter().sorted().collect());
-                }
-            }
-        }
-
-        clusters
+    /// Find all the sets of three mutually interconnected computers, using [`Graph::triangles`]
+    fn trios(&self) -> HashSet<Vec<&'a str>> {
+        self.links
+            .triangles()
+            .iter()
+            .map(|trio| self.links.nodes_for(trio).into_iter().sorted().collect())
+            .collect()
     }
 
     /// Uses [`Network::trios`] to find all clusters of three, and filters to only those where at least one computer
     /// starts with the provided character
-    fn clusters_containing(&self, char: &str) -> Vec<Vec<&str>> {
+    fn clusters_containing(&self, char: &str) -> Vec<Vec<&'a str>> {
         self.trios()
-            .iter()
+            .into_iter()
             .filter(|cluster| cluster.iter().any(|node| node.starts_with(char)))
-            .cloned()
             .collect()
     }
 
-    /// Given a starting computer id and the list of it's direct connections, find all that are also mutually
-    /// interconnected
-    fn find_fully_connected_cluster(
-        &self,
-        start: &'a str,
-        connected: &HashSet<&'a str>,
-    ) -> Vec<&str> {
-        let mut cluster = vec![start];
-        for computer in connected {
-            if cluster.iter().all(|b| self.links[computer].contains(b)) {
-                cluster.push(*computer);
-            }
-        }
-        cluster
-    }
-
-    /// For each node find a cluster that is fully interconnected, and then take the biggest and turn it into a
+    /// Find the biggest cluster that is fully interconnected with [`Graph::maximum_clique`], and then turn it into a
     /// password.
     fn find_lan_password(&self) -> String {
         self.links
-            .iter()
-            .map(|(&start, connected)| self.find_fully_connected_cluster(start, connected))
-            .max_by_key(|c| c.len())
-            .unwrap()
+            .nodes_for(&self.links.maximum_clique())
             .iter()
             .sorted()
             .join(",")
     }
 }
 
-/// Build a network from lines like `ab-cd` denoting that `ab` is directly connected to `cd`.
-fn parse_input(input: &String) -> Network {
-    let mut links: HashMap<&str, HashSet<&str>> = HashMap::new();
-
-    for (a, b) in input.lines().map(|line| line.split_once("-").unwrap()) {
-        links.entry(a).or_default().insert(b);
-        links.entry(b).or_default().insert(a);
-    }
-
-    Network { links }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::day_23::*;
-    use crate::helpers::test::assert_contains_in_any_order;
-
-    fn example_network() -> Network<'static> {
-        let links = vec![
-            ("kh", vec!["tc", "qp", "ub", "ta"].into_iter().collect()),
-            ("tc", vec!["kh", "wh", "td", "co"].into_iter().collect()),
-            ("qp", vec!["kh", "ub", "td", "wh"].into_iter().collect()),
-            ("de", vec!["cg", "co", "ta", "ka"].into_iter().collect()),
-            ("cg", vec!["de", "tb", "yn", "aq"].into_iter().collect()),
-            ("ka", vec!["co", "tb", "ta", "de"].into_iter().collect()),
-            ("co", vec!["ka", "ta", "de", "tc"].into_iter().collect()),
-            ("yn", vec!["aq", "cg", "wh", "td"].into_iter().collect()),
-            ("aq", vec!["yn", "vc", "cg", "wq"].into_iter().collect()),
-            ("ub", vec!["qp", "kh", "wq", "vc"].into_iter().collect()),
-            ("tb", vec!["cg", "ka", "wq", "vc"].into_iter().collect()),
-            ("vc", vec!["aq", "ub", "wq", "tb"].into_iter().collect()),
-            ("wh", vec!["tc", "td", "yn", "qp"].into_iter().collect()),
-            ("ta", vec!["co", "ka", "de", "kh"].into_iter().collect()),
-            ("td", vec!["tc", "wh", "qp", "yn"].into_iter().collect()),
-            ("wq", vec!["tb", "ub", "aq", "vc"].into_iter().collect()),
-        ]
-        .into_iter()
-        .collect();
-
-        Network { links }
-    }
-
-    #[test]
-    fn can_parse_input() {
-        let input = "kh-tc
+/// The example network from the puzzle description, used for [`figures`]
+const EXAMPLE: &str = "kh-tc
 qp-kh
 de-cg
 ka-co
@@ -165,15 +100,116 @@ co-tc
 wh-qp
 tb-vc
 td-yn
-"
-        .to_string();
+";
+
+/// Draw the example network with the computers in a ring, and the largest clique, i.e. the LAN party, highlighted
+pub fn figures() -> Vec<Figure> {
+    let input = EXAMPLE.to_string();
+    let network = parse_input(&input);
+    let graph = &network.links;
+    let clique: HashSet<NodeId> = graph.maximum_clique().into_iter().collect();
+
+    let size = 320.0;
+    let radius = 130.0;
+    let ids: Vec<NodeId> = graph
+        .node_ids()
+        .sorted_by_key(|&id| graph.node(id))
+        .collect();
+    let position = |id: NodeId| {
+        let index = ids.iter().position(|&other| other == id).unwrap();
+        let angle = index as f64 / ids.len() as f64 * std::f64::consts::TAU;
+        (
+            size / 2.0 + radius * angle.sin(),
+            size / 2.0 - radius * angle.cos(),
+        )
+    };
+
+    let mut svg = Svg::new(size, size);
+    for &a in &ids {
+        for &b in graph.neighbours(a).iter().filter(|&&b| b > a) {
+            let (colour, width) = if clique.contains(&a) && clique.contains(&b) {
+                ("#d81b60", 3.0)
+            } else {
+                ("#bbb", 1.0)
+            };
+            svg.line(position(a), position(b), colour, width);
+        }
+    }
+    for &id in &ids {
+        let fill = if clique.contains(&id) {
+            "#f8bbd0"
+        } else {
+            "#e3f2fd"
+        };
+        svg.circle(position(id), 14.0, fill);
+        svg.text(position(id), graph.node(id), "#000");
+    }
+
+    vec![Figure {
+        day: 23,
+        name: "largest-clique",
+        svg: svg.render(),
+    }]
+}
+
+/// Build a network from lines like `ab-cd` denoting that `ab` is directly connected to `cd`.
+fn parse_input(input: &String) -> Network<'_> {
+    let mut links = Graph::undirected();
+
+    for (a, b) in input.lines().map(|line| line.split_once("-").unwrap()) {
+        links.add_edge(a, b);
+    }
+
+    Network { links }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_23::*;
+    use crate::helpers::test::assert_contains_in_any_order;
+
+    fn example_links() -> Vec<(&'static str, Vec<&'static str>)> {
+        vec![
+            ("kh", vec!["tc", "qp", "ub", "ta"]),
+            ("tc", vec!["kh", "wh", "td", "co"]),
+            ("qp", vec!["kh", "ub", "td", "wh"]),
+            ("de", vec!["cg", "co", "ta", "ka"]),
+            ("cg", vec!["de", "tb", "yn", "aq"]),
+            ("ka", vec!["co", "tb", "ta", "de"]),
+            ("co", vec!["ka", "ta", "de", "tc"]),
+            ("yn", vec!["aq", "cg", "wh", "td"]),
+            ("aq", vec!["yn", "vc", "cg", "wq"]),
+            ("ub", vec!["qp", "kh", "wq", "vc"]),
+            ("tb", vec!["cg", "ka", "wq", "vc"]),
+            ("vc", vec!["aq", "ub", "wq", "tb"]),
+            ("wh", vec!["tc", "td", "yn", "qp"]),
+            ("ta", vec!["co", "ka", "de", "kh"]),
+            ("td", vec!["tc", "wh", "qp", "yn"]),
+            ("wq", vec!["tb", "ub", "aq", "vc"]),
+        ]
+    }
+
+    fn example_network() -> Network<'static> {
+        let mut links = Graph::undirected();
+        for (a, connected) in example_links() {
+            for b in connected {
+                links.add_edge(a, b);
+            }
+        }
+
+        Network { links }
+    }
+
+    #[test]
+    fn can_parse_input() {
+        let input = EXAMPLE.to_string();
 
-        let actual = parse_input(&input);
-        let expected = example_network();
+        let actual = parse_input(&input).links;
 
-        assert_contains_in_any_order(actual.links.keys(), expected.links.keys());
-        for (key, value) in actual.links {
-            assert_contains_in_any_order(&value, expected.links.get(key).unwrap())
+        assert_eq!(actual.len(), 16);
+        for (computer, connected) in example_links() {
+            let id = actual.id(&computer).unwrap();
+            assert_contains_in_any_order(actual.nodes_for(actual.neighbours(id)), connected);
         }
     }
 
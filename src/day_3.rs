@@ -4,23 +4,22 @@
 //! which cherry-picks all the [`Mul`] instructions. [`sum_instructions`] extends that by respecting [`Do`] and
 //! [`Dont`] instructions.
 
+use crate::runner::Runner;
 use regex::{Captures, Regex};
-use std::fs;
 use Instruction::*;
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-3-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 3.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-3-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
 
     let instructions = extract_instructions(&contents);
-    println!("Sum of mul instructions: {}", sum_muls(&instructions));
-    println!(
-        "Sum of all instructions: {}",
+    runner.part(1, "Sum of mul instructions: {}", || sum_muls(&instructions));
+    runner.part(2, "Sum of all instructions: {}", || {
         sum_instructions(&instructions)
-    );
+    });
 }
 
 /// The possible instructions that can be extracted from the input string
@@ -91,7 +90,7 @@ fn sum_instructions(instructions: &Vec<Instruction>) -> u32 {
 #[cfg(test)]
 mod tests {
     use crate::day_3::*;
-    
+
     #[test]
     fn can_extract_muls() {
         assert_eq!(
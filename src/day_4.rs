@@ -8,25 +8,24 @@
 //! [`Wordsearch::count_x_masses`] solves part 2, using [`Wordsearch::is_x_mas`], which in turn reuses some of the
 //! part 1 helpers
 
+use crate::runner::Runner;
 use itertools::Itertools;
-use std::fs;
 use std::str::FromStr;
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-4-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 4.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-4-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
 
     let wordsearch = Wordsearch::from_str(&contents).unwrap();
 
-    println!(
-        "There are {} XMASes",
+    runner.part(1, "There are {} XMASes", || {
         wordsearch.word_count(&"XMAS".to_string())
-    );
+    });
 
-    println!("There are {} X-MASes", wordsearch.count_x_masses());
+    runner.part(2, "There are {} X-MASes", || wordsearch.count_x_masses());
 }
 
 /// A wordsearch grid
@@ -149,7 +148,7 @@ impl FromStr for Wordsearch {
 #[cfg(test)]
 mod tests {
     use crate::day_4::*;
-    
+
     #[test]
     fn can_parse_input() {
         let input = "..X...
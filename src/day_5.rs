@@ -8,48 +8,45 @@
 //!
 //! Part 2 is solved by [`sort_and_sum_invalid_middle_pages`], with [`sort_pages`] doing the extra work, everything
 //! else is reused from part 1.
+//!
+//! The rules are stored as a directed [`Graph`] of pages.
 
-use itertools::Itertools;
-use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet};
-use std::fs;
+use crate::helpers::graph::Graph;
+use crate::runner::Runner;
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-5-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 5.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-5-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
 
     let (rules, updates) = parse_input(&contents);
 
-    println!(
-        "The sum of valid middle page numbers is {}",
+    runner.part(1, "The sum of valid middle page numbers is {}", || {
         sum_valid_middle_pages(&updates, &rules)
-    );
+    });
 
-    println!(
+    runner.part(
+        2,
         "The sum of sorted invalid middle page numbers is {}",
-        sort_and_sum_invalid_middle_pages(&updates, &rules)
+        || sort_and_sum_invalid_middle_pages(&updates, &rules),
     );
 }
 
-/// Rules represented as a lookup from page number to the set of pages that must come after
-type Rules = HashMap<u32, HashSet<u32>>;
+/// Rules represented as a directed graph, with an edge from each page to each page that must come after it
+type Rules = Graph<u32>;
 /// Updates as a list of page numbers
 type Update = Vec<u32>;
 
-/// Build the Rules lookup by adding each rule in turn.
+/// Build the Rules graph by adding each rule in turn.
 fn parse_rules(input: &str) -> Rules {
-    let mut rules: Rules = HashMap::new();
+    let mut rules: Rules = Graph::directed();
     input
         .lines()
         .flat_map(|line| line.split_once("|"))
         .for_each(|(before, after)| {
-            let key = before.parse().unwrap();
-            let value = after.parse().unwrap();
-
-            rules.entry(key).or_default().insert(value);
+            rules.add_edge(before.parse().unwrap(), after.parse().unwrap())
         });
 
     rules
@@ -73,18 +70,10 @@ fn parse_input(input: &String) -> (Rules, Vec<Update>) {
 /// For a given [`Update`], check for each page that a page already in the update list does not need to come after
 /// the current page.
 fn validate_update(update: &Update, rules: &Rules) -> bool {
-    let mut seen = HashSet::new();
-    let empty = HashSet::new();
-
-    for page in update {
-        let rule = rules.get(page).unwrap_or(&empty);
-        if seen.intersection(rule).next().is_some() {
-            return false;
-        }
-        seen.insert(*page);
-    }
-
-    true
+    update
+        .iter()
+        .enumerate()
+        .all(|(i, page)| !update[..i].iter().any(|seen| rules.has_edge(page, seen)))
 }
 
 /// Utility to get the middle page of an update.
@@ -102,27 +91,15 @@ fn sum_valid_middle_pages(updates: &Vec<Update>, rules: &Rules) -> u32 {
         .sum()
 }
 
-/// Assuming all pairs of pages have rules that specify an ordering for that pair, use that to provide a sorting
-/// function for [`Itertools::sorted_by`].
+/// The full set of rules has cycles, but the rules between the pages of a single update don't, so take the
+/// [`Graph::subgraph`] of just those pages and sort it with [`Graph::topological_sort`].
 fn sort_pages(update: &Update, rules: &Rules) -> Update {
-    let empty = HashSet::new();
-
-    update
-        .iter()
-        .sorted_by(|page_a, page_b| {
-            let rule_a = rules.get(page_a).unwrap_or(&empty);
-            let rule_b = rules.get(page_b).unwrap_or(&empty);
+    let relevant_rules = rules.subgraph(update);
+    let order = relevant_rules
+        .topological_sort()
+        .expect("The rules for an update should not have cycles");
 
-            if rule_a.contains(page_b) {
-                Ordering::Less
-            } else if rule_b.contains(page_a) {
-                Ordering::Greater
-            } else {
-                Ordering::Equal
-            }
-        })
-        .cloned()
-        .collect()
+    relevant_rules.nodes_for(&order)
 }
 
 /// Solution to part 2 - similar to [`sum_valid_middle_pages`], but finds invalid pages and sorts them before
@@ -140,18 +117,22 @@ fn sort_and_sum_invalid_middle_pages(updates: &Vec<Update>, rules: &Rules) -> u3
 mod tests {
     use crate::day_5::*;
     use crate::helpers::test::assert_contains_in_any_order;
-    
+
     fn example_rules() -> Rules {
-        vec![
-            (97, vec![13, 61, 47, 29, 53, 75].into_iter().collect()),
-            (75, vec![29, 53, 47, 61, 13].into_iter().collect()),
-            (61, vec![13, 53, 29].into_iter().collect()),
-            (29, vec![13].into_iter().collect()),
-            (53, vec![29, 13].into_iter().collect()),
-            (47, vec![53, 13, 61, 29].into_iter().collect()),
-        ]
-        .into_iter()
-        .collect()
+        let mut rules = Graph::directed();
+        for (before, afters) in [
+            (97, vec![13, 61, 47, 29, 53, 75]),
+            (75, vec![29, 53, 47, 61, 13]),
+            (61, vec![13, 53, 29]),
+            (29, vec![13]),
+            (53, vec![29, 13]),
+            (47, vec![53, 13, 61, 29]),
+        ] {
+            for after in afters {
+                rules.add_edge(before, after);
+            }
+        }
+        rules
     }
 
     fn example_updates() -> Vec<Update> {
@@ -199,7 +180,17 @@ mod tests {
 
         let (rules, updates) = parse_input(&input);
 
-        assert_contains_in_any_order(rules, example_rules());
+        let expected = example_rules();
+        assert_eq!(rules.len(), expected.len());
+        for before in expected.node_ids().map(|id| *expected.node(id)) {
+            for after in expected.node_ids().map(|id| *expected.node(id)) {
+                assert_eq!(
+                    rules.has_edge(&before, &after),
+                    expected.has_edge(&before, &after),
+                    "{before}|{after}"
+                );
+            }
+        }
         assert_contains_in_any_order(updates, example_updates());
     }
 
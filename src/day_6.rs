@@ -9,29 +9,31 @@
 //! [`count_obstructions_causing_loops`] is the solution to part 2, using [`is_loop`] along with reusing some of part 1.
 
 use crate::day_6::Direction::*;
+use crate::progress::Progress;
+use crate::runner::Runner;
 use itertools::Itertools;
 use rayon::prelude::*;
 use std::collections::HashSet;
-use std::fs;
 use std::iter::successors;
+use tracing::{debug, Span};
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-6-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 6.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-6-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let (lab, guard) = parse_input(&contents);
 
-    println!(
-        "The guard visits {} positions",
+    runner.part(1, "The guard visits {} positions", || {
         count_guard_positions(&guard, &lab)
-    );
+    });
 
-    println!(
+    runner.part(
+        2,
         "There are {} positions where obstructions will cause a loop",
-        count_obstructions_causing_loops(&guard, &lab)
-    )
+        || count_obstructions_causing_loops(&guard, &lab),
+    );
 }
 
 /// The direction the guard is facing
@@ -187,25 +189,44 @@ fn count_guard_positions(guard: &Guard, lab: &Lab) -> usize {
 
 /// Will the guard end up in an infinite loop for the provided lab and starting position
 fn is_loop(guard: &Guard, lab: &Lab) -> bool {
-    route_iter(&guard, &lab).duplicates().next().is_some()
+    route_iter(&guard, &lab)
+        .duplicates()
+        .next()
+        .inspect(|g| debug!(position = ?g.position, direction = ?g.direction, "loop detected"))
+        .is_some()
 }
 
 /// Try adding obstacles to all locations on the guard's route, and see which ones cause the guard to end up in an
 /// infinite loop
+///
+/// The current tracing span is re-entered in the rayon workers, so that events from [`is_loop`] are attributed to this
+/// day and part.
+///
+/// The candidate positions are collected first, so that [`Progress`] can be reported out of the total to check.
 fn count_obstructions_causing_loops(guard: &Guard, lab: &Lab) -> usize {
-    route_iter(guard, lab)
+    let span = Span::current();
+    let candidates: Vec<(Guard, Position)> = route_iter(guard, lab)
         .flat_map(|g| Some(g).zip(g.next_position(lab)))
         .filter(|(_, pos)| *pos != guard.position)
         .unique_by(|(_, pos)| *pos)
+        .collect();
+
+    let progress = Progress::new("day 6", candidates.len() as u64);
+    candidates
+        .into_iter()
         .par_bridge()
-        .filter(|(g, pos)| is_loop(g, &lab.with_obstruction(*pos)))
+        .filter(|(g, pos)| {
+            let causes_loop = span.in_scope(|| is_loop(g, &lab.with_obstruction(*pos)));
+            progress.add(1);
+            causes_loop
+        })
         .count()
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day_6::*;
-    
+
     fn example_lab() -> Lab {
         Lab {
             width: 10,
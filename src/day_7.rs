@@ -5,28 +5,26 @@
 //! [`calculate_calibration_total`] uses [`is_solvable`] to solve both parts, [`part_1_operations`] and
 //! [`part_2_operations`] providing the different operation lists.
 
+use crate::runner::Runner;
 use rayon::prelude::*;
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
-use std::fs;
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-7-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 7.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-7-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let equations = parse_input(&contents);
 
-    println!(
-        "The calibration total is {}",
+    runner.part(1, "The calibration total is {}", || {
         calculate_calibration_total(&equations, &part_1_operations())
-    );
+    });
 
-    println!(
-        "The calibration total with concatenation is {}",
+    runner.part(2, "The calibration total with concatenation is {}", || {
         calculate_calibration_total(&equations, &part_2_operations())
-    );
+    });
 }
 
 /// An operation to apply with the running total on the lhs, and the next number as the rhs.
@@ -154,7 +152,7 @@ fn calculate_calibration_total(equations: &Vec<Equation>, ops: &Vec<Operation>)
 mod tests {
     use crate::day_7::*;
     use itertools::Itertools;
-    
+
     fn example_equations() -> Vec<Equation> {
         vec![
             Equation::new(190, 10, vec![19]),
@@ -7,32 +7,32 @@
 //! [`find_antinodes_for_frequency`] for each frequency in the map. This in turn uses [`find_antinodes_for_pair`] on
 //! each combination of antenna in the frequency group.
 //!
-//! [`sequence_from_antenna`] extrapolates the line defined by a pair of antenna, in one direction and
+//! [`sequence_from_antenna`] extrapolates the line defined by a pair of antenna, in one direction, stepping by the
+//! delta reduced by its [`gcd`] so that positions between grid-aligned multiples aren't skipped, and
 //! [`antinode_pair_sequence_modifier`] and [`resonant_harmonies_sequence_modifier`] handle selecting the right
 //! node(s) for part 1 and 2 respectively. [`find_antinodes_for_pair`] uses [`sequence_from_antenna`] starting from
 //! each node in the pair.
 
+use crate::helpers::number_theory::gcd;
+use crate::runner::Runner;
 use itertools::{iterate, Itertools};
 use std::collections::HashMap;
-use std::fs;
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-8-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 8.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-8-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let antenna_map = parse_input(&contents);
 
-    println!(
-        "There are {} unique antinodes",
+    runner.part(1, "There are {} unique antinodes", || {
         count_antinodes_for_map(&antenna_map, antinode_pair_sequence_modifier)
-    );
+    });
 
-    println!(
-        "There are {} unique antinodes",
+    runner.part(2, "There are {} unique antinodes", || {
         count_antinodes_for_map(&antenna_map, resonant_harmonies_sequence_modifier)
-    );
+    });
 }
 
 /// A coordinate on the grid
@@ -69,8 +69,8 @@ fn parse_input(input: &String) -> AntennaMap {
 }
 
 /// This differentiates the two parts by allowing outside control over which nodes are selected when extrapolating
-/// the line between two antenna
-type SequenceModifier = fn(Vec<Coordinate>) -> Vec<Coordinate>;
+/// the line between two antenna. The second argument is the number of grid steps in the delta between the pair.
+type SequenceModifier = fn(Vec<Coordinate>, usize) -> Vec<Coordinate>;
 
 /// Extrapolate from a point along a delta whilst it's within the bounds of the antenna map
 fn sequence_from_antenna(
@@ -90,6 +90,10 @@ fn sequence_from_antenna(
 
 /// Fine the antinodes by determining the coordinate delta between two antinodes, extrapolating the line from both
 /// ends, applying the SequenceModifier relevant to the part being solved.
+///
+/// The delta is reduced by its [`gcd`] so that every grid position on the line is visited, including any between the
+/// pair. Each sequence starts at one antenna and passes through the other, so the whole line is covered, and the
+/// overlap is removed.
 fn find_antinodes_for_pair(
     (r1, c1): Coordinate,
     (r2, c2): Coordinate,
@@ -98,24 +102,36 @@ fn find_antinodes_for_pair(
 ) -> Vec<Coordinate> {
     let dr = r1 as isize - r2 as isize;
     let dc = c1 as isize - c2 as isize;
+    let steps = gcd(dr.unsigned_abs() as u64, dc.unsigned_abs() as u64) as isize;
+    let (dr, dc) = (dr / steps, dc / steps);
 
-    let increasing = sequence_from_antenna((r1, c1).clone(), (dr, dc).clone(), bounds);
-    let decreasing = sequence_from_antenna((r2, c2), (-dr, -dc), bounds);
+    let increasing = sequence_from_antenna((r2, c2), (dr, dc), bounds);
+    let decreasing = sequence_from_antenna((r1, c1), (-dr, -dc), bounds);
 
-    [sequence_modifier(increasing), sequence_modifier(decreasing)].concat()
+    sequence_modifier(increasing, steps as usize)
+        .into_iter()
+        .chain(sequence_modifier(decreasing, steps as usize))
+        .unique()
+        .collect()
 }
 
-/// Part 1 - Select only the first node beyond the origin
-fn antinode_pair_sequence_modifier(coordinate_sequence: Vec<Coordinate>) -> Vec<Coordinate> {
+/// Part 1 - Select only the node one full delta beyond the other antenna, i.e. two deltas from the origin
+fn antinode_pair_sequence_modifier(
+    coordinate_sequence: Vec<Coordinate>,
+    steps: usize,
+) -> Vec<Coordinate> {
     coordinate_sequence
         .into_iter()
-        .dropping(1)
+        .dropping(2 * steps)
         .take(1)
         .collect()
 }
 
 /// Part 2 - Select all nodes including the origin - essentially the identity function
-fn resonant_harmonies_sequence_modifier(coordinate_sequence: Vec<Coordinate>) -> Vec<Coordinate> {
+fn resonant_harmonies_sequence_modifier(
+    coordinate_sequence: Vec<Coordinate>,
+    _steps: usize,
+) -> Vec<Coordinate> {
     coordinate_sequence
 }
 
@@ -148,7 +164,7 @@ fn count_antinodes_for_map(antenna_map: &AntennaMap, sequence_modifier: Sequence
 mod tests {
     use crate::day_8::*;
     use crate::helpers::test::assert_contains_in_any_order;
-    
+
     #[test]
     fn can_parse_input() {
         let input = "............
@@ -221,6 +237,15 @@ mod tests {
             ),
             vec![(5, 1), (4, 3), (3, 5), (2, 7), (1, 9)],
         );
+        assert_contains_in_any_order(
+            find_antinodes_for_pair(
+                (1, 1),
+                (3, 3),
+                &(6, 6),
+                resonant_harmonies_sequence_modifier,
+            ),
+            vec![(0, 0), (1, 1), (2, 2), (3, 3), (4, 4), (5, 5)],
+        );
     }
 
     #[test]
@@ -7,27 +7,25 @@
 //! files. [`fill_space_with_fragmentation`] Is the logic for filling in disk space for part 1,
 //! [`fill_space_without_fragmentation`] for part 2.
 
+use crate::runner::Runner;
 use std::collections::VecDeque;
-use std::fs;
 use DiskUsage::*;
 
 /// The entry point for running the solutions with the 'real' puzzle input.
 ///
 /// - The puzzle input is expected to be at `<project_root>/res/day-9-input`
 /// - It is expected this will be called by [`super::main()`] when the user elects to run day 9.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-9-input.txt").expect("Failed to read file");
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
     let disk_map = parse_input(&contents);
 
-    println!(
-        "The checksum is {}",
+    runner.part(1, "The checksum is {}", || {
         calculate_checksum(&disk_map, fill_space_with_fragmentation)
-    );
+    });
 
-    println!(
-        "The checksum is {}",
+    runner.part(2, "The checksum is {}", || {
         calculate_checksum(&disk_map, fill_space_without_fragmentation)
-    );
+    });
 }
 
 /// A file on disk
@@ -228,7 +226,7 @@ fn calculate_checksum(disk_map: &VecDeque<DiskUsage>, space_filler: SpaceFiller)
 mod tests {
     use crate::day_9::*;
     use crate::helpers::test::assert_contains_in_any_order;
-    
+
     fn example_disk() -> VecDeque<DiskUsage> {
         vec![
             DiskUsage::new_file(0, 0, 2),
@@ -0,0 +1,486 @@
+//! Puzzle inputs can't be published, so the input for each day is committed to `inputs/day-N.enc` encrypted, along
+//! with the answers that were confirmed for it. The key is read from the `AOC_INPUT_KEY` environment variable, e.g. a
+//! CI secret, or from `res/input_key.txt`, which isn't committed.
+//!
+//! [`crate::runner::Runner::input`] falls back to decrypting the input when `res/day-N-input.txt` is missing, and
+//! [`check`] re-runs each day against its encrypted input to catch regressions. Without the key, the check is skipped
+//! rather than failing, so that it can run on a fresh clone.
+
+use crate::answer_cache::AnswerCache;
+use crate::report::{OutputFormat, Status};
+use crate::runner::{run_isolated_with_input, Day, RunOptions, DEFAULT_TIMEOUT};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where the key is read from if the environment variable isn't set, this is not committed
+pub const KEY_PATH: &str = "res/input_key.txt";
+/// The environment variable the key is read from first
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+
+/// The length of the nonce stored at the start of each encrypted file
+const NONCE_LENGTH: usize = 12;
+
+/// What is stored encrypted for each day
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct SealedInput {
+    pub input: String,
+    /// The confirmed answer for each part, if there was one when the input was encrypted
+    pub answers: [Option<String>; 2],
+}
+
+/// A ChaCha20-Poly1305 key, stored as hex
+pub struct InputKey(Key);
+
+impl InputKey {
+    /// A new random key
+    pub fn generate() -> InputKey {
+        InputKey(ChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    pub fn from_hex(hex: &str) -> Result<InputKey, String> {
+        let hex = hex.trim();
+        if hex.len() != 64 {
+            return Err("The key should be 64 hex digits".to_string());
+        }
+
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| "The key should be 64 hex digits".to_string())?;
+
+        Ok(InputKey(*Key::from_slice(&bytes)))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    /// Read the key from [`KEY_VAR`], or [`KEY_PATH`] under `root`, None if it is in neither
+    pub fn load(root: &Path) -> Result<Option<InputKey>, String> {
+        let hex = env::var(KEY_VAR)
+            .ok()
+            .filter(|hex| !hex.trim().is_empty())
+            .or_else(|| fs::read_to_string(root.join(KEY_PATH)).ok());
+
+        hex.map(|hex| InputKey::from_hex(&hex)).transpose()
+    }
+
+    pub fn encrypt(&self, sealed: &SealedInput) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let plaintext = serde_json::to_vec(sealed).unwrap();
+        let ciphertext = ChaCha20Poly1305::new(&self.0)
+            .encrypt(&nonce, plaintext.as_slice())
+            .expect("Failed to encrypt input");
+
+        [nonce.as_slice(), &ciphertext].concat()
+    }
+
+    pub fn decrypt(&self, bytes: &[u8]) -> Result<SealedInput, String> {
+        if bytes.len() < NONCE_LENGTH {
+            return Err("The encrypted input is truncated".to_string());
+        }
+
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LENGTH);
+        let plaintext = ChaCha20Poly1305::new(&self.0)
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| "Failed to decrypt input, check the key".to_string())?;
+
+        serde_json::from_slice(&plaintext).map_err(|e| e.to_string())
+    }
+}
+
+/// Where a day's encrypted input is committed
+pub fn sealed_path(root: &Path, day: u8) -> PathBuf {
+    root.join(format!("inputs/day-{day}.enc"))
+}
+
+fn read_sealed(root: &Path, day: u8, key: &InputKey) -> Option<Result<SealedInput, String>> {
+    let bytes = fs::read(sealed_path(root, day)).ok()?;
+    Some(key.decrypt(&bytes))
+}
+
+/// Decrypt a day's input, if there is an encrypted copy and the key is available
+pub fn decrypt_input(root: &Path, day: u8) -> Option<String> {
+    let key = InputKey::load(root)
+        .map_err(|e| eprintln!("Warning: {e}"))
+        .ok()??;
+
+    match read_sealed(root, day, &key)? {
+        Ok(sealed) => Some(sealed.input),
+        Err(e) => {
+            eprintln!("Warning: day {day}: {e}");
+            None
+        }
+    }
+}
+
+/// Encrypt the input and confirmed answers of each day with an input in `res/`, generating a key if there isn't one
+/// yet. Files that already hold the same input and answers are left alone, so that re-running this doesn't change
+/// them. Returns the days that were written.
+pub fn encrypt_inputs(root: &Path, cache: &AnswerCache) -> Result<Vec<u8>, String> {
+    let key = match InputKey::load(root)? {
+        Some(key) => key,
+        None => {
+            let key = InputKey::generate();
+            fs::create_dir_all(root.join("res")).map_err(|e| e.to_string())?;
+            fs::write(root.join(KEY_PATH), key.to_hex()).map_err(|e| e.to_string())?;
+            println!("Generated a new key in {KEY_PATH}");
+            key
+        }
+    };
+
+    fs::create_dir_all(root.join("inputs")).map_err(|e| e.to_string())?;
+
+    let mut written = Vec::new();
+    for day in 1..=25 {
+        let Ok(input) = fs::read_to_string(root.join(format!("res/day-{day}-input.txt"))) else {
+            continue;
+        };
+        let sealed = SealedInput {
+            answers: [1, 2].map(|part| {
+                cache
+                    .confirmed_answer(day, part, &input)
+                    .map(|answer| answer.to_string())
+            }),
+            input,
+        };
+
+        if read_sealed(root, day, &key).is_some_and(|existing| existing.as_ref() == Ok(&sealed)) {
+            continue;
+        }
+
+        fs::write(sealed_path(root, day), key.encrypt(&sealed)).map_err(|e| e.to_string())?;
+        written.push(day);
+    }
+
+    Ok(written)
+}
+
+/// How one part of a day compared with its known answer
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum CheckOutcome {
+    Passed,
+    Failed {
+        expected: String,
+        actual: String,
+    },
+    /// The solution panicked, or didn't report an answer for the part
+    NoAnswer,
+    /// The solution ran for longer than the day's time limit
+    TimedOut,
+    /// The day's encrypted input couldn't be decrypted, so none of its parts were checked
+    Unreadable(String),
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct CheckRow {
+    pub day: u8,
+    /// The part checked, or `None` for a problem with the whole day
+    pub part: Option<u8>,
+    pub outcome: CheckOutcome,
+}
+
+impl Display for CheckRow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.part {
+            Some(part) => write!(f, "Day {} part {part}: ", self.day)?,
+            None => write!(f, "Day {}: ", self.day)?,
+        }
+        match &self.outcome {
+            CheckOutcome::Passed => write!(f, "ok"),
+            CheckOutcome::Failed { expected, actual } => {
+                write!(f, "expected {expected}, got {actual}")
+            }
+            CheckOutcome::NoAnswer => write!(f, "no answer"),
+            CheckOutcome::TimedOut => write!(f, "timed out"),
+            CheckOutcome::Unreadable(e) => write!(f, "couldn't read the encrypted input, {e}"),
+        }
+    }
+}
+
+/// Run a day against its sealed input, comparing each part that has a known answer. Answers are always recalculated,
+/// and the cache used is thrown away. The day is run with [`run_isolated_with_input`], so a day that panics or runs
+/// for longer than its time limit fails the check rather than stopping or hanging it.
+fn check_day(solution: &Day, sealed: &SealedInput) -> Vec<CheckRow> {
+    let options = RunOptions {
+        force: true,
+        format: OutputFormat::Json,
+        ..RunOptions::default()
+    };
+    let mut cache = AnswerCache::new("unused.json");
+    let results = run_isolated_with_input(
+        solution,
+        Some(sealed.input.clone()),
+        options,
+        &mut cache,
+        DEFAULT_TIMEOUT,
+    );
+
+    (1..=2)
+        .filter_map(|part| {
+            let expected = sealed.answers[part as usize - 1].clone()?;
+            let outcome = match results.iter().find(|result| result.part == part) {
+                Some(result) if result.status == Status::TimedOut => CheckOutcome::TimedOut,
+                Some(result) if result.status == Status::Panicked => CheckOutcome::NoAnswer,
+                Some(result) if result.answer == expected => CheckOutcome::Passed,
+                Some(result) => CheckOutcome::Failed {
+                    expected,
+                    actual: result.answer.clone(),
+                },
+                None => CheckOutcome::NoAnswer,
+            };
+
+            Some(CheckRow {
+                day: solution.day,
+                part: Some(part),
+                outcome,
+            })
+        })
+        .collect()
+}
+
+/// Check each day that has an encrypted input with [`check_day`], printing the rows as they are found. A day whose
+/// input can't be decrypted is reported as a failed row, and the other days are still checked.
+fn check_days(days: &[Day], root: &Path, key: &InputKey) -> Vec<CheckRow> {
+    let mut rows = Vec::new();
+    for solution in days {
+        let day_rows = match read_sealed(root, solution.day, key) {
+            Some(Ok(sealed)) => check_day(solution, &sealed),
+            Some(Err(e)) => vec![CheckRow {
+                day: solution.day,
+                part: None,
+                outcome: CheckOutcome::Unreadable(e),
+            }],
+            None => continue,
+        };
+        day_rows.iter().for_each(|row| println!("{row}"));
+        rows.extend(day_rows);
+    }
+
+    rows
+}
+
+/// Re-run every day that has an encrypted input, and compare its answers with those confirmed when it was encrypted.
+/// Returns false if any didn't match. This is skipped if the key isn't available.
+pub fn check(days: &[Day], root: &Path) -> Result<bool, String> {
+    let Some(key) = InputKey::load(root)? else {
+        println!(
+            "No key in ${KEY_VAR} or {KEY_PATH}, skipping the checks against encrypted inputs"
+        );
+        return Ok(true);
+    };
+
+    let rows = check_days(days, root, &key);
+    let failed = rows
+        .iter()
+        .filter(|row| row.outcome != CheckOutcome::Passed)
+        .count();
+    println!("{} passed, {failed} failed", rows.len() - failed);
+
+    Ok(failed == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer_cache::CacheKey;
+    use crate::encrypted_inputs::*;
+    use crate::runner::Runner;
+    use std::env::temp_dir;
+    use std::time::Duration;
+
+    fn sealed(input: &str, answers: [Option<&str>; 2]) -> SealedInput {
+        SealedInput {
+            input: input.to_string(),
+            answers: answers.map(|answer| answer.map(|answer| answer.to_string())),
+        }
+    }
+
+    /// Sums the numbers in the input for part 1, and panics for part 2 if any are negative
+    fn run_example(runner: &mut Runner) {
+        let input = runner.input();
+        let numbers: Vec<i32> = input.lines().map(|line| line.parse().unwrap()).collect();
+        runner.part(1, "Sum: {}", || numbers.iter().sum::<i32>());
+        runner.part(2, "Max: {}", || {
+            assert!(numbers.iter().all(|&n| n >= 0));
+            *numbers.iter().max().unwrap()
+        });
+    }
+
+    #[test]
+    fn can_round_trip_key_and_input() {
+        let key = InputKey::generate();
+        let hex = key.to_hex();
+        assert_eq!(hex.len(), 64);
+        assert_eq!(
+            InputKey::from_hex(&format!("{hex}\n")).unwrap().to_hex(),
+            hex
+        );
+        assert!(InputKey::from_hex("abc").is_err());
+        assert!(InputKey::from_hex(&"zz".repeat(32)).is_err());
+
+        let input = sealed("3   4\n4   3\n", [Some("11"), None]);
+        let encrypted = key.encrypt(&input);
+        assert!(!String::from_utf8_lossy(&encrypted).contains("3   4"));
+        assert_eq!(key.decrypt(&encrypted).unwrap(), input);
+
+        assert!(InputKey::generate().decrypt(&encrypted).is_err());
+        assert!(key.decrypt(&encrypted[..5]).is_err());
+    }
+
+    #[test]
+    fn can_encrypt_and_decrypt_inputs() {
+        // The key in the environment would be used instead of the one generated in the temporary root
+        if env::var(KEY_VAR).is_ok() {
+            return;
+        }
+
+        let root = temp_dir().join(format!("aoc-encrypted-{}", std::process::id()));
+        fs::create_dir_all(root.join("res")).unwrap();
+        fs::write(root.join("res/day-1-input.txt"), "1\n2\n").unwrap();
+        fs::write(root.join("res/day-3-input.txt"), "5\n").unwrap();
+
+        let mut cache = AnswerCache::new("unused.json");
+        let key = CacheKey::new(1, 2, "1\n2\n", "1");
+        cache.insert(key.clone(), "2".to_string());
+        cache.confirm(&key);
+
+        assert_eq!(encrypt_inputs(&root, &cache).unwrap(), vec![1, 3]);
+        assert!(root.join(KEY_PATH).exists());
+        assert_eq!(decrypt_input(&root, 1), Some("1\n2\n".to_string()));
+        assert_eq!(decrypt_input(&root, 2), None);
+
+        let key = InputKey::load(&root).unwrap().unwrap();
+        let bytes = fs::read(sealed_path(&root, 1)).unwrap();
+        assert_eq!(
+            key.decrypt(&bytes).unwrap(),
+            sealed("1\n2\n", [None, Some("2")])
+        );
+
+        // Unchanged inputs aren't rewritten
+        assert!(encrypt_inputs(&root, &cache).unwrap().is_empty());
+        assert_eq!(fs::read(sealed_path(&root, 1)).unwrap(), bytes);
+
+        // Without the key, the encrypted inputs can't be read
+        fs::remove_file(root.join(KEY_PATH)).unwrap();
+        assert_eq!(decrypt_input(&root, 1), None);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn can_check_day_against_known_answers() {
+        let day = Day::new(1, "1", run_example);
+
+        assert_eq!(
+            check_day(&day, &sealed("1\n2\n", [Some("3"), Some("2")])),
+            vec![
+                CheckRow {
+                    day: 1,
+                    part: Some(1),
+                    outcome: CheckOutcome::Passed
+                },
+                CheckRow {
+                    day: 1,
+                    part: Some(2),
+                    outcome: CheckOutcome::Passed
+                },
+            ]
+        );
+        assert_eq!(
+            check_day(&day, &sealed("1\n2\n", [Some("4"), None])),
+            vec![CheckRow {
+                day: 1,
+                part: Some(1),
+                outcome: CheckOutcome::Failed {
+                    expected: "4".to_string(),
+                    actual: "3".to_string()
+                }
+            }]
+        );
+        assert_eq!(
+            check_day(&day, &sealed("-1\n2\n", [None, Some("2")])),
+            vec![CheckRow {
+                day: 1,
+                part: Some(2),
+                outcome: CheckOutcome::NoAnswer
+            }]
+        );
+    }
+
+    #[test]
+    fn can_time_out_checking_day() {
+        let day = Day::new(1, "1", |runner| {
+            runner.part(1, "Forever: {}", || -> u32 {
+                loop {
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+            });
+        })
+        .with_timeout(Duration::from_millis(50));
+
+        assert_eq!(
+            check_day(&day, &sealed("1\n", [Some("1"), None])),
+            vec![CheckRow {
+                day: 1,
+                part: Some(1),
+                outcome: CheckOutcome::TimedOut
+            }]
+        );
+    }
+
+    #[test]
+    fn can_carry_on_checking_after_unreadable_input() {
+        let root = temp_dir().join(format!("aoc-encrypted-check-{}", std::process::id()));
+        fs::create_dir_all(root.join("inputs")).unwrap();
+        let key = InputKey::generate();
+        fs::write(sealed_path(&root, 1), b"not encrypted").unwrap();
+        fs::write(
+            sealed_path(&root, 2),
+            key.encrypt(&sealed("1\n2\n", [Some("3"), None])),
+        )
+        .unwrap();
+
+        let days = [
+            Day::new(1, "1", run_example),
+            Day::new(2, "1", run_example),
+            Day::new(3, "1", run_example),
+        ];
+        assert_eq!(
+            check_days(&days, &root, &key),
+            vec![
+                CheckRow {
+                    day: 1,
+                    part: None,
+                    outcome: CheckOutcome::Unreadable(
+                        "Failed to decrypt input, check the key".to_string()
+                    )
+                },
+                CheckRow {
+                    day: 2,
+                    part: Some(1),
+                    outcome: CheckOutcome::Passed
+                },
+            ]
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn can_skip_check_without_key() {
+        let root = temp_dir().join(format!("aoc-encrypted-skip-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+
+        if env::var(KEY_VAR).is_err() {
+            assert_eq!(check(&[Day::new(1, "1", run_example)], &root), Ok(true));
+        }
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
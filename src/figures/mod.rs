@@ -0,0 +1,224 @@
+//! SVG figures for the write-ups in `pubs`, drawn by running selected solutions on the puzzle's example input.
+//!
+//! Each day that has figures provides a `figures` function returning a [`Figure`] for each one. `--figures` writes them
+//! to `pubs/assets/images/day_N/<name>.svg`, and posts include them by day and name with the `figure` shortcode, e.g.
+//! `{% figure 12, "regions", "The example garden's regions" %}`, so the names are kept stable.
+//!
+//! [`Svg`] is a minimal builder for the few shapes the figures need, with [`cell`] and [`cell_centre`] to lay out
+//! grid based puzzles.
+
+use crate::{day_12, day_16, day_20, day_23};
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Where figures are written, relative to the project root
+pub const FIGURES_DIR: &str = "pubs/assets/images";
+/// The size in pixels of one grid square
+pub const CELL_SIZE: f64 = 20.0;
+
+/// A figure for a day's write-up
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Figure {
+    pub day: u8,
+    /// The stable name posts use to refer to the figure, also its file name without the extension
+    pub name: &'static str,
+    pub svg: String,
+}
+
+/// Escape text for use in SVG content or attributes
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Round a coordinate to two decimal places, so the output isn't cluttered with floating point noise
+fn number(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// An SVG document built up one shape at a time
+#[derive(PartialEq, Debug, Clone)]
+pub struct Svg {
+    width: f64,
+    height: f64,
+    elements: Vec<String>,
+}
+
+impl Svg {
+    /// An empty drawing of the given size in pixels
+    pub fn new(width: f64, height: f64) -> Svg {
+        Svg {
+            width,
+            height,
+            elements: Vec::new(),
+        }
+    }
+
+    /// An empty drawing sized to fit a grid with `rows` and `columns` of [`CELL_SIZE`] squares
+    pub fn for_grid(rows: usize, columns: usize) -> Svg {
+        Svg::new(columns as f64 * CELL_SIZE, rows as f64 * CELL_SIZE)
+    }
+
+    /// A filled rectangle with its top left corner at `(x, y)`
+    pub fn rect(&mut self, (x, y): (f64, f64), (width, height): (f64, f64), fill: &str) {
+        let (x, y, width, height) = (number(x), number(y), number(width), number(height));
+        self.elements.push(format!(
+            r#"<rect x="{x}" y="{y}" width="{width}" height="{height}" fill="{fill}"/>"#
+        ));
+    }
+
+    /// A straight line between two points
+    pub fn line(&mut self, (x1, y1): (f64, f64), (x2, y2): (f64, f64), stroke: &str, width: f64) {
+        let (x1, y1, x2, y2) = (number(x1), number(y1), number(x2), number(y2));
+        self.elements.push(format!(
+            r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="{stroke}" stroke-width="{width}" stroke-linecap="round"/>"#
+        ));
+    }
+
+    /// A filled circle centred on `(x, y)`
+    pub fn circle(&mut self, (x, y): (f64, f64), radius: f64, fill: &str) {
+        let (x, y) = (number(x), number(y));
+        self.elements.push(format!(
+            r#"<circle cx="{x}" cy="{y}" r="{radius}" fill="{fill}"/>"#
+        ));
+    }
+
+    /// A label centred on `(x, y)`
+    pub fn text(&mut self, (x, y): (f64, f64), text: &str, fill: &str) {
+        let (x, y) = (number(x), number(y));
+        self.elements.push(format!(
+            r#"<text x="{x}" y="{y}" fill="{fill}" font-family="monospace" font-size="12" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+            escape(text)
+        ));
+    }
+
+    /// The finished document
+    pub fn render(&self) -> String {
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+            self.width, self.height
+        );
+        svg.push('\n');
+        for element in &self.elements {
+            writeln!(svg, "  {element}").unwrap();
+        }
+        svg.push_str("</svg>\n");
+
+        svg
+    }
+}
+
+/// The top left corner of a grid square
+pub fn cell((r, c): (usize, usize)) -> (f64, f64) {
+    (c as f64 * CELL_SIZE, r as f64 * CELL_SIZE)
+}
+
+/// The centre of a grid square
+pub fn cell_centre(coordinates: (usize, usize)) -> (f64, f64) {
+    let (x, y) = cell(coordinates);
+    (x + CELL_SIZE / 2.0, y + CELL_SIZE / 2.0)
+}
+
+/// A distinct colour for each of `count` items, spread around the colour wheel
+pub fn palette(index: usize, count: usize) -> String {
+    format!("hsl({}, 60%, 75%)", index * 360 / count.max(1))
+}
+
+/// Every figure from every day that has them
+fn all_figures() -> Vec<Figure> {
+    [
+        day_12::figures(),
+        day_16::figures(),
+        day_20::figures(),
+        day_23::figures(),
+    ]
+    .concat()
+}
+
+/// Where a figure is written to under `root`
+pub fn figure_path(root: &Path, day: u8, name: &str) -> PathBuf {
+    root.join(FIGURES_DIR)
+        .join(format!("day_{day}"))
+        .join(format!("{name}.svg"))
+}
+
+/// Write the figures, or just those for `day`, under `root`, returning the paths written
+pub fn export_figures(root: &Path, day: Option<u8>) -> io::Result<Vec<PathBuf>> {
+    all_figures()
+        .into_iter()
+        .filter(|figure| day.is_none_or(|day| day == figure.day))
+        .map(|figure| {
+            let path = figure_path(root, figure.day, figure.name);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, figure.svg)?;
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::figures::*;
+    use std::env::temp_dir;
+
+    #[test]
+    fn can_render_svg() {
+        let mut svg = Svg::for_grid(2, 3);
+        svg.rect(cell((1, 2)), (CELL_SIZE, CELL_SIZE), "red");
+        svg.line(cell_centre((0, 0)), cell_centre((1, 1)), "black", 2.0);
+        svg.circle(cell_centre((0, 1)), 4.5, "blue");
+        svg.text((5.0, 2.0 / 3.0), "a<b", "black");
+
+        assert_eq!(
+            svg.render(),
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="60" height="40" viewBox="0 0 60 40">
+  <rect x="40" y="20" width="20" height="20" fill="red"/>
+  <line x1="10" y1="10" x2="30" y2="30" stroke="black" stroke-width="2" stroke-linecap="round"/>
+  <circle cx="30" cy="10" r="4.5" fill="blue"/>
+  <text x="5" y="0.67" fill="black" font-family="monospace" font-size="12" text-anchor="middle" dominant-baseline="central">a&lt;b</text>
+</svg>
+"#
+        );
+    }
+
+    #[test]
+    fn figure_names_are_unique() {
+        let figures = all_figures();
+        for (i, figure) in figures.iter().enumerate() {
+            assert!(
+                !figures[i + 1..]
+                    .iter()
+                    .any(|other| other.day == figure.day && other.name == figure.name),
+                "day {} has more than one {} figure",
+                figure.day,
+                figure.name
+            );
+        }
+    }
+
+    #[test]
+    fn can_export_figures() {
+        let root = temp_dir().join(format!("aoc-figures-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        let written = export_figures(&root, Some(23)).unwrap();
+        assert_eq!(written, vec![figure_path(&root, 23, "largest-clique")]);
+        assert_eq!(
+            written[0],
+            root.join("pubs/assets/images/day_23/largest-clique.svg")
+        );
+        assert!(fs::read_to_string(&written[0]).unwrap().starts_with("<svg"));
+
+        let written = export_figures(&root, None).unwrap();
+        assert_eq!(written.len(), all_figures().len());
+        assert!(written.iter().all(|path| path.exists()));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,266 @@
+//! An external merge sort for `u32`s, for lists too big to sort in memory, e.g. day 1's location lists.
+//!
+//! An [`ExternalSorter`] buffers values until it has `chunk_size` of them, then sorts the buffer and spills it to a
+//! run file in its own temporary directory. [`ExternalSorter::finish`] spills the last partial chunk and returns the
+//! [`SortedRuns`], which can be read back in order as many times as needed with [`SortedRuns::iter`], merging the runs
+//! with a heap holding one value per run. So that reading doesn't need a file open for every run, `finish` first
+//! merges groups of runs into longer runs until there are at most [`MAX_FAN_IN`]. Memory use is bounded by the chunk
+//! size plus a read buffer per run, and the temporary directory is removed when the [`SortedRuns`] is dropped.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The most runs that are read at once, keeping well within the usual limit of 1024 open files even when several
+/// [`SortedRuns`] are read together
+pub const MAX_FAN_IN: usize = 64;
+
+/// Used to give each sorter in the process its own directory
+static NEXT_SORTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Sorts values pushed to it one at a time, spilling sorted runs of them to disk
+#[derive(Debug)]
+pub struct ExternalSorter {
+    chunk_size: usize,
+    buffer: Vec<u32>,
+    runs: SortedRuns,
+    /// The number of run files created so far, used to name the next one
+    runs_written: usize,
+}
+
+impl ExternalSorter {
+    /// A sorter that holds at most `chunk_size` values in memory, with its run files in a new directory under the
+    /// system's temporary directory
+    pub fn new(chunk_size: usize) -> io::Result<ExternalSorter> {
+        let dir = std::env::temp_dir().join(format!(
+            "aoc-external-sort-{}-{}",
+            std::process::id(),
+            NEXT_SORTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir)?;
+
+        Ok(ExternalSorter {
+            chunk_size: chunk_size.max(1),
+            buffer: Vec::with_capacity(chunk_size.max(1)),
+            runs: SortedRuns {
+                dir,
+                paths: Vec::new(),
+            },
+            runs_written: 0,
+        })
+    }
+
+    /// Add a value, spilling the buffer to a new run if it is full
+    pub fn push(&mut self, value: u32) -> io::Result<()> {
+        self.buffer.push(value);
+        if self.buffer.len() >= self.chunk_size {
+            self.spill()?;
+        }
+
+        Ok(())
+    }
+
+    /// Sort the buffered values and write them to a run file as little-endian bytes
+    fn spill(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.sort_unstable();
+        let path = self.write_run(buffer.iter().copied().map(Ok))?;
+
+        self.runs.paths.push(path);
+        buffer.clear();
+        self.buffer = buffer;
+
+        Ok(())
+    }
+
+    /// Write sorted values to a new run file as little-endian bytes, returning its path
+    fn write_run(&mut self, values: impl Iterator<Item = io::Result<u32>>) -> io::Result<PathBuf> {
+        let path = self.runs.dir.join(format!("run-{}.bin", self.runs_written));
+        self.runs_written += 1;
+
+        let mut writer = BufWriter::new(File::create(&path)?);
+        for value in values {
+            writer.write_all(&value?.to_le_bytes())?;
+        }
+        writer.flush()?;
+
+        Ok(path)
+    }
+
+    /// Spill any remaining values, then merge the runs in groups of [`MAX_FAN_IN`] until there are few enough to read
+    /// at once, and return them ready to be merged
+    pub fn finish(mut self) -> io::Result<SortedRuns> {
+        self.spill()?;
+
+        while self.runs.paths.len() > MAX_FAN_IN {
+            let paths = std::mem::take(&mut self.runs.paths);
+            for group in paths.chunks(MAX_FAN_IN) {
+                let path = self.write_run(MergedRuns::open(group)?)?;
+                for run in group {
+                    fs::remove_file(run)?;
+                }
+                self.runs.paths.push(path);
+            }
+        }
+
+        Ok(self.runs)
+    }
+}
+
+/// The sorted run files written by an [`ExternalSorter`]
+#[derive(Debug)]
+pub struct SortedRuns {
+    dir: PathBuf,
+    paths: Vec<PathBuf>,
+}
+
+impl SortedRuns {
+    /// Read all the values back in ascending order, by merging the runs
+    pub fn iter(&self) -> io::Result<MergedRuns> {
+        MergedRuns::open(&self.paths)
+    }
+}
+
+impl Drop for SortedRuns {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Read the next value from a run, or `None` at the end of it
+fn read_value(reader: &mut impl Read) -> io::Result<Option<u32>> {
+    let mut bytes = [0; 4];
+    match reader.read_exact(&mut bytes) {
+        Ok(()) => Ok(Some(u32::from_le_bytes(bytes))),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// An iterator over the values in [`SortedRuns`] in ascending order
+#[derive(Debug)]
+pub struct MergedRuns {
+    readers: Vec<BufReader<File>>,
+    /// The smallest value not yet returned from each run that has any left, with the index of that run
+    heap: BinaryHeap<Reverse<(u32, usize)>>,
+}
+
+impl MergedRuns {
+    /// Start merging the run files at `paths`, which are all kept open until the merge is dropped
+    fn open(paths: &[PathBuf]) -> io::Result<MergedRuns> {
+        let mut readers = paths
+            .iter()
+            .map(|path| File::open(path).map(BufReader::new))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let mut heap = BinaryHeap::with_capacity(readers.len());
+        for (run, reader) in readers.iter_mut().enumerate() {
+            if let Some(value) = read_value(reader)? {
+                heap.push(Reverse((value, run)));
+            }
+        }
+
+        Ok(MergedRuns { readers, heap })
+    }
+}
+
+impl Iterator for MergedRuns {
+    type Item = io::Result<u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((value, run)) = self.heap.pop()?;
+        match read_value(&mut self.readers[run]) {
+            Ok(Some(next)) => self.heap.push(Reverse((next, run))),
+            Ok(None) => {}
+            Err(e) => return Some(Err(e)),
+        }
+
+        Some(Ok(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::external_sort::*;
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    fn sort(values: &[u32], chunk_size: usize) -> Vec<u32> {
+        let mut sorter = ExternalSorter::new(chunk_size).unwrap();
+        for &value in values {
+            sorter.push(value).unwrap();
+        }
+        let runs = sorter.finish().unwrap();
+
+        runs.iter().unwrap().collect::<io::Result<_>>().unwrap()
+    }
+
+    #[test]
+    fn can_sort_across_runs() {
+        assert_eq!(
+            sort(&[5, 3, 9, 1, 3, 7, 0, 8], 3),
+            vec![0, 1, 3, 3, 5, 7, 8, 9]
+        );
+        assert_eq!(sort(&[], 3), Vec::<u32>::new());
+        assert_eq!(sort(&[u32::MAX, 0], 1), vec![0, u32::MAX]);
+    }
+
+    #[test]
+    fn can_read_runs_more_than_once() {
+        let mut sorter = ExternalSorter::new(2).unwrap();
+        for value in [4, 2, 3, 1] {
+            sorter.push(value).unwrap();
+        }
+        let runs = sorter.finish().unwrap();
+
+        let first: Vec<u32> = runs.iter().unwrap().map(Result::unwrap).collect();
+        let second: Vec<u32> = runs.iter().unwrap().map(Result::unwrap).collect();
+        assert_eq!(first, vec![1, 2, 3, 4]);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn can_limit_the_runs_read_at_once() {
+        let values: Vec<u32> = (0..5000).map(|i| i * 7919 % 5003).collect();
+        let mut sorter = ExternalSorter::new(1).unwrap();
+        for &value in &values {
+            sorter.push(value).unwrap();
+        }
+        let runs = sorter.finish().unwrap();
+
+        assert!(runs.paths.len() <= MAX_FAN_IN);
+        assert_eq!(fs::read_dir(&runs.dir).unwrap().count(), runs.paths.len());
+
+        let sorted: Vec<u32> = runs.iter().unwrap().map(Result::unwrap).collect();
+        assert_eq!(sorted, values.into_iter().sorted().collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn removes_run_files_when_dropped() {
+        let mut sorter = ExternalSorter::new(1).unwrap();
+        sorter.push(1).unwrap();
+        let runs = sorter.finish().unwrap();
+        let dir = runs.dir.clone();
+        assert!(dir.join("run-0.bin").exists());
+
+        drop(runs);
+        assert!(!dir.exists());
+    }
+
+    proptest! {
+        #[test]
+        fn sorts_like_sort_unstable(values in prop::collection::vec(any::<u32>(), 0..200), chunk_size in 1usize..20) {
+            let mut expected = values.clone();
+            expected.sort_unstable();
+
+            prop_assert_eq!(sort(&values, chunk_size), expected);
+        }
+    }
+}
@@ -0,0 +1,361 @@
+//! A directed or undirected [`Graph`], for puzzles that boil down to rules or networks between named things, e.g.
+//! day 5's page ordering rules and day 23's LAN links.
+//!
+//! Nodes are interned, so any hashable value such as a `&str` or a `u32` can be used as a node, and algorithms work
+//! with the resulting [`NodeId`]s, which are handed out in the order nodes are first seen. Results are returned in a
+//! deterministic order, sorted by [`NodeId`] unless noted otherwise.
+//!
+//! Alongside building and querying the graph there are [`Graph::topological_sort`] and the clique utilities
+//! [`Graph::triangles`] and [`Graph::maximum_clique`].
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::hash::Hash;
+
+/// The index of an interned node in a [`Graph`]
+pub type NodeId = usize;
+
+/// A graph with nodes of type `N`, where edges either have a direction or link both nodes equally
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    directed: bool,
+    nodes: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    edges: HashSet<(NodeId, NodeId)>,
+    outgoing: Vec<Vec<NodeId>>,
+    incoming: Vec<Vec<NodeId>>,
+}
+
+impl<N> Graph<N>
+where
+    N: Eq + Hash + Clone,
+{
+    fn new(directed: bool) -> Self {
+        Graph {
+            directed,
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+            edges: HashSet::new(),
+            outgoing: Vec::new(),
+            incoming: Vec::new(),
+        }
+    }
+
+    /// An empty graph where edges go from one node to another
+    pub fn directed() -> Self {
+        Self::new(true)
+    }
+
+    /// An empty graph where edges link both nodes
+    pub fn undirected() -> Self {
+        Self::new(false)
+    }
+
+    /// The number of nodes
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Add a node if it hasn't been seen before, returning its id either way
+    pub fn intern(&mut self, node: N) -> NodeId {
+        if let Some(&id) = self.ids.get(&node) {
+            return id;
+        }
+
+        let id = self.nodes.len();
+        self.nodes.push(node.clone());
+        self.ids.insert(node, id);
+        self.outgoing.push(Vec::new());
+        self.incoming.push(Vec::new());
+        id
+    }
+
+    /// The id of a node, if it is in the graph
+    pub fn id(&self, node: &N) -> Option<NodeId> {
+        self.ids.get(node).copied()
+    }
+
+    /// The node an id was interned from
+    pub fn node(&self, id: NodeId) -> &N {
+        &self.nodes[id]
+    }
+
+    /// Map a list of ids back to their nodes
+    pub fn nodes_for(&self, ids: &[NodeId]) -> Vec<N> {
+        ids.iter().map(|&id| self.nodes[id].clone()).collect()
+    }
+
+    /// All the node ids, in the order they were interned
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeId> {
+        0..self.nodes.len()
+    }
+
+    fn add_arc(&mut self, from: NodeId, to: NodeId) {
+        if self.edges.insert((from, to)) {
+            self.outgoing[from].push(to);
+            self.incoming[to].push(from);
+        }
+    }
+
+    /// Add an edge between two nodes, interning them as needed. Adding an edge that already exists has no effect.
+    pub fn add_edge(&mut self, from: N, to: N) {
+        let from = self.intern(from);
+        let to = self.intern(to);
+
+        self.add_arc(from, to);
+        if !self.directed {
+            self.add_arc(to, from);
+        }
+    }
+
+    /// Whether there is an edge from `from` to `to`, either way round for an undirected graph
+    pub fn has_edge(&self, from: &N, to: &N) -> bool {
+        self.id(from)
+            .zip(self.id(to))
+            .is_some_and(|ids| self.edges.contains(&ids))
+    }
+
+    /// The ids reached by following an edge out of `id`, in the order the edges were added
+    pub fn neighbours(&self, id: NodeId) -> &[NodeId] {
+        &self.outgoing[id]
+    }
+
+    /// The graph restricted to `nodes`, keeping only the edges between them. Nodes are interned in the order given,
+    /// including any that aren't in this graph.
+    pub fn subgraph<'a>(&self, nodes: impl IntoIterator<Item = &'a N>) -> Graph<N>
+    where
+        N: 'a,
+    {
+        let mut subgraph = Graph::new(self.directed);
+        for node in nodes {
+            subgraph.intern(node.clone());
+        }
+
+        for (from, to) in self.edges.iter().copied() {
+            if let (Some(new_from), Some(new_to)) =
+                (subgraph.id(&self.nodes[from]), subgraph.id(&self.nodes[to]))
+            {
+                subgraph.add_arc(new_from, new_to);
+            }
+        }
+
+        subgraph
+    }
+
+    /// Order the nodes so that every edge goes from an earlier node to a later one, using Kahn's algorithm. Where
+    /// more than one node could come next, the lowest id is taken. Returns `None` if there is a cycle.
+    pub fn topological_sort(&self) -> Option<Vec<NodeId>> {
+        let mut in_degree: Vec<usize> = self.incoming.iter().map(|from| from.len()).collect();
+        let mut ready: BTreeSet<NodeId> =
+            self.node_ids().filter(|&id| in_degree[id] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+
+        while let Some(id) = ready.pop_first() {
+            order.push(id);
+            for &next in &self.outgoing[id] {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.insert(next);
+                }
+            }
+        }
+
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Whether every pair of the given nodes is linked, in both directions for a directed graph
+    fn is_clique(&self, ids: &[NodeId]) -> bool {
+        ids.iter().enumerate().all(|(i, &a)| {
+            ids[i + 1..]
+                .iter()
+                .all(|&b| self.edges.contains(&(a, b)) && self.edges.contains(&(b, a)))
+        })
+    }
+
+    /// All the sets of three mutually linked nodes, each sorted, in order
+    pub fn triangles(&self) -> Vec<[NodeId; 3]> {
+        let mut triangles = Vec::new();
+        for a in self.node_ids() {
+            let mut after_a: Vec<NodeId> = self.outgoing[a]
+                .iter()
+                .copied()
+                .filter(|&b| b > a)
+                .collect();
+            after_a.sort();
+            for (i, &b) in after_a.iter().enumerate() {
+                for &c in &after_a[i + 1..] {
+                    if self.is_clique(&[a, b, c]) {
+                        triangles.push([a, b, c]);
+                    }
+                }
+            }
+        }
+
+        triangles
+    }
+
+    /// The largest set of mutually linked nodes, sorted, found with the Bron–Kerbosch algorithm with pivoting. If
+    /// there are several of the same size, the first found is returned.
+    pub fn maximum_clique(&self) -> Vec<NodeId> {
+        // Only count a link if it goes both ways, so this also works for directed graphs
+        let linked: Vec<HashSet<NodeId>> = self
+            .node_ids()
+            .map(|a| {
+                self.outgoing[a]
+                    .iter()
+                    .copied()
+                    .filter(|&b| b != a && self.edges.contains(&(b, a)))
+                    .collect()
+            })
+            .collect();
+
+        fn bron_kerbosch(
+            linked: &[HashSet<NodeId>],
+            clique: &mut Vec<NodeId>,
+            mut candidates: HashSet<NodeId>,
+            mut excluded: HashSet<NodeId>,
+            best: &mut Vec<NodeId>,
+        ) {
+            if candidates.is_empty() {
+                if excluded.is_empty() && clique.len() > best.len() {
+                    *best = clique.clone();
+                }
+                return;
+            }
+
+            let pivot = candidates
+                .union(&excluded)
+                .max_by_key(|&&id| linked[id].len())
+                .copied()
+                .unwrap();
+            let mut to_try: Vec<NodeId> = candidates.difference(&linked[pivot]).copied().collect();
+            to_try.sort();
+
+            for id in to_try {
+                clique.push(id);
+                bron_kerbosch(
+                    linked,
+                    clique,
+                    candidates.intersection(&linked[id]).copied().collect(),
+                    excluded.intersection(&linked[id]).copied().collect(),
+                    best,
+                );
+                clique.pop();
+                candidates.remove(&id);
+                excluded.insert(id);
+            }
+        }
+
+        let mut best = Vec::new();
+        bron_kerbosch(
+            &linked,
+            &mut Vec::new(),
+            self.node_ids().collect(),
+            HashSet::new(),
+            &mut best,
+        );
+        best.sort();
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::graph::*;
+
+    fn directed(edges: &[(u32, u32)]) -> Graph<u32> {
+        let mut graph = Graph::directed();
+        for &(from, to) in edges {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    fn undirected(edges: &[(&'static str, &'static str)]) -> Graph<&'static str> {
+        let mut graph = Graph::undirected();
+        for &(a, b) in edges {
+            graph.add_edge(a, b);
+        }
+        graph
+    }
+
+    #[test]
+    fn can_intern_nodes() {
+        let mut graph = Graph::directed();
+        assert_eq!(graph.intern("a"), 0);
+        assert_eq!(graph.intern("b"), 1);
+        assert_eq!(graph.intern("a"), 0);
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph.id(&"b"), Some(1));
+        assert_eq!(graph.id(&"c"), None);
+        assert_eq!(graph.node(1), &"b");
+    }
+
+    #[test]
+    fn can_add_edges() {
+        let graph = directed(&[(1, 2), (2, 3), (1, 2)]);
+        assert!(graph.has_edge(&1, &2));
+        assert!(!graph.has_edge(&2, &1));
+        assert!(!graph.has_edge(&1, &4));
+        assert_eq!(graph.neighbours(0), &[1]);
+
+        let graph = undirected(&[("a", "b")]);
+        assert!(graph.has_edge(&"a", &"b"));
+        assert!(graph.has_edge(&"b", &"a"));
+    }
+
+    #[test]
+    fn can_take_subgraph() {
+        let graph = directed(&[(1, 2), (2, 3), (3, 4)]);
+        let subgraph = graph.subgraph(&[3, 2, 5]);
+
+        assert_eq!(subgraph.nodes_for(&[0, 1, 2]), vec![3, 2, 5]);
+        assert!(subgraph.has_edge(&2, &3));
+        assert!(!subgraph.has_edge(&3, &4));
+        assert_eq!(subgraph.edges.len(), 1);
+    }
+
+    #[test]
+    fn can_sort_topologically() {
+        let graph = directed(&[(5, 3), (3, 1), (5, 1), (4, 1)]);
+        assert_eq!(
+            graph
+                .topological_sort()
+                .map(|order| graph.nodes_for(&order)),
+            Some(vec![5, 3, 4, 1])
+        );
+
+        assert_eq!(directed(&[(1, 2), (2, 3), (3, 1)]).topological_sort(), None);
+    }
+
+    #[test]
+    fn can_find_cliques() {
+        let graph = undirected(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("a", "c"),
+            ("c", "d"),
+            ("d", "e"),
+            ("c", "e"),
+            ("b", "d"),
+            ("b", "e"),
+        ]);
+
+        assert!(graph.is_clique(&[0, 1, 2]));
+        assert!(!graph.is_clique(&[0, 1, 3]));
+        assert_eq!(
+            graph.triangles(),
+            vec![[0, 1, 2], [1, 2, 3], [1, 2, 4], [1, 3, 4], [2, 3, 4]]
+        );
+        assert_eq!(
+            graph.nodes_for(&graph.maximum_clique()),
+            vec!["b", "c", "d", "e"]
+        );
+
+        // A one-way edge doesn't count as a link
+        assert!(!directed(&[(1, 2)]).is_clique(&[0, 1]));
+        assert_eq!(
+            directed(&[(1, 2), (2, 1), (2, 3)]).maximum_clique(),
+            vec![0, 1]
+        );
+    }
+}
@@ -1,7 +1,11 @@
+pub mod external_sort;
+pub mod graph;
+pub mod number_theory;
+
 #[cfg(test)]
 pub(crate) mod test {
     use std::fmt::Debug;
-    
+
     #[allow(dead_code)]
     pub(crate) fn assert_contains_in_any_order<T>(
         actual: impl IntoIterator<Item = T>,
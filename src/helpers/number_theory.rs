@@ -0,0 +1,236 @@
+//! Number theory shared between the puzzle days: [`gcd`], the [`chinese_remainder`] theorem, and
+//! [`solve_linear_system`] for exact integer solutions.
+//!
+//! Intermediate values are calculated as `i128`, and results that don't fit back into 64 bits are reported as `None`
+//! or [`LinearSystemError::Overflow`] rather than wrapping.
+
+/// The greatest common divisor, `gcd(0, 0)` is 0
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The gcd `g` of `a` and `b`, along with the Bézout coefficients `x` and `y` where `a * x + b * y = g`
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a.abs(), a.signum(), 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a.rem_euclid(b));
+        (g, y, x - a.div_euclid(b) * y)
+    }
+}
+
+/// Solve a system of congruences `x ≡ residue (mod modulus)`, returning the smallest non-negative solution, and the
+/// modulus it repeats with, the lcm of the moduli. The moduli don't need to be coprime, but this is `None` if the
+/// congruences conflict, a modulus isn't positive, or the combined modulus overflows.
+pub fn chinese_remainder(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences
+        .iter()
+        .try_fold((0i128, 1i128), |(x, m), &(residue, modulus)| {
+            if modulus <= 0 {
+                return None;
+            }
+            let (residue, modulus) = (
+                (residue as i128).rem_euclid(modulus as i128),
+                modulus as i128,
+            );
+
+            let (g, inverse, _) = extended_gcd(m, modulus);
+            let difference = residue - x;
+            if difference % g != 0 {
+                return None;
+            }
+
+            let combined = m / g * modulus;
+            if combined > i64::MAX as i128 {
+                return None;
+            }
+
+            let step = (difference / g * inverse).rem_euclid(modulus / g);
+            Some(((x + m * step).rem_euclid(combined), combined))
+        })
+        .map(|(x, m)| (x as i64, m as i64))
+}
+
+/// Why [`solve_linear_system`] couldn't find a solution
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum LinearSystemError {
+    /// The matrix isn't square, or doesn't match the length of the right-hand side
+    Shape,
+    /// There isn't a unique solution
+    Singular,
+    /// There is a unique solution, but it isn't all integers
+    NotIntegral,
+    /// The calculation, or the solution, doesn't fit in the integer types
+    Overflow,
+}
+
+/// Solve `matrix · x = rhs` exactly for integer `x`, using fraction-free (Bareiss) elimination so that there is no
+/// rounding to check for.
+pub fn solve_linear_system(
+    matrix: &[Vec<i64>],
+    rhs: &[i64],
+) -> Result<Vec<i64>, LinearSystemError> {
+    use LinearSystemError::*;
+
+    let n = rhs.len();
+    if matrix.len() != n || matrix.iter().any(|row| row.len() != n) {
+        return Err(Shape);
+    }
+
+    // The augmented matrix
+    let mut a: Vec<Vec<i128>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, &b)| row.iter().chain([&b]).map(|&v| v as i128).collect())
+        .collect();
+
+    let mut previous_pivot = 1i128;
+    for k in 0..n {
+        let pivot_row = (k..n).find(|&i| a[i][k] != 0).ok_or(Singular)?;
+        a.swap(k, pivot_row);
+
+        for i in k + 1..n {
+            for j in k + 1..=n {
+                let product = a[i][j].checked_mul(a[k][k]).ok_or(Overflow)?;
+                let cross = a[i][k].checked_mul(a[k][j]).ok_or(Overflow)?;
+                // Exact by Sylvester's identity
+                a[i][j] = product.checked_sub(cross).ok_or(Overflow)? / previous_pivot;
+            }
+            a[i][k] = 0;
+        }
+        previous_pivot = a[k][k];
+    }
+
+    let mut solution = vec![0i128; n];
+    for i in (0..n).rev() {
+        let mut numerator = a[i][n];
+        for j in i + 1..n {
+            let term = a[i][j].checked_mul(solution[j]).ok_or(Overflow)?;
+            numerator = numerator.checked_sub(term).ok_or(Overflow)?;
+        }
+        if numerator % a[i][i] != 0 {
+            return Err(NotIntegral);
+        }
+        solution[i] = numerator / a[i][i];
+    }
+
+    solution
+        .into_iter()
+        .map(|x| i64::try_from(x).map_err(|_| Overflow))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::number_theory::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn can_find_gcd() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(gcd(0, 0), 0);
+    }
+
+    #[test]
+    fn can_solve_congruences() {
+        assert_eq!(
+            chinese_remainder(&[(2, 3), (3, 5), (2, 7)]),
+            Some((23, 105))
+        );
+        assert_eq!(chinese_remainder(&[(2, 7), (6, 11)]), Some((72, 77)));
+        assert_eq!(chinese_remainder(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(chinese_remainder(&[(1, 4), (2, 6)]), None);
+        assert_eq!(chinese_remainder(&[(1, 0)]), None);
+        assert_eq!(chinese_remainder(&[]), Some((0, 1)));
+        assert_eq!(chinese_remainder(&[(0, i64::MAX), (1, i64::MAX - 1)]), None);
+    }
+
+    #[test]
+    fn can_solve_linear_systems() {
+        // Day 13's first example machine
+        assert_eq!(
+            solve_linear_system(&[vec![94, 22], vec![34, 67]], &[8400, 5400]),
+            Ok(vec![80, 40])
+        );
+        assert_eq!(
+            solve_linear_system(&[vec![26, 67], vec![66, 21]], &[12748, 12176]),
+            Err(LinearSystemError::NotIntegral)
+        );
+        assert_eq!(
+            solve_linear_system(&[vec![0, 1], vec![1, 0]], &[3, 4]),
+            Ok(vec![4, 3])
+        );
+        assert_eq!(
+            solve_linear_system(&[vec![1, 2], vec![2, 4]], &[3, 6]),
+            Err(LinearSystemError::Singular)
+        );
+        assert_eq!(
+            solve_linear_system(&[vec![1, 2]], &[3]),
+            Err(LinearSystemError::Shape)
+        );
+        assert_eq!(
+            solve_linear_system(&[vec![1]], &[i64::MAX]),
+            Ok(vec![i64::MAX])
+        );
+        assert_eq!(
+            solve_linear_system(&[vec![-1]], &[i64::MIN]),
+            Err(LinearSystemError::Overflow)
+        );
+    }
+
+    /// The determinant of a 3 x 3 matrix, to know whether a system should be solvable
+    fn determinant(m: &[Vec<i64>]) -> i64 {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    proptest! {
+        #[test]
+        fn gcd_divides_both(a in 0u64..1_000_000, b in 0u64..1_000_000) {
+            let g = gcd(a, b);
+            if g != 0 {
+                prop_assert_eq!(a % g, 0);
+                prop_assert_eq!(b % g, 0);
+                prop_assert_eq!(gcd(a / g, b / g), 1);
+            }
+        }
+
+        #[test]
+        fn chinese_remainder_recovers_value(
+            x in 0i64..1_000_000_000,
+            moduli in prop::collection::vec(1i64..1000, 1..4)
+        ) {
+            let congruences: Vec<(i64, i64)> = moduli.iter().map(|&m| (x % m, m)).collect();
+            let (solution, modulus) = chinese_remainder(&congruences).unwrap();
+
+            let expected_modulus = moduli.iter().fold(1, |acc, &m| acc / gcd(acc, m as u64) * m as u64);
+            prop_assert_eq!(modulus as u64, expected_modulus);
+            prop_assert_eq!(solution, x % modulus);
+        }
+
+        #[test]
+        fn linear_system_recovers_solution(
+            values in prop::collection::vec(-1000i64..1000, 9),
+            x in prop::collection::vec(-1_000_000i64..1_000_000, 3)
+        ) {
+            let matrix: Vec<Vec<i64>> = values.chunks(3).map(|row| row.to_vec()).collect();
+            let rhs: Vec<i64> = matrix
+                .iter()
+                .map(|row| row.iter().zip(&x).map(|(a, b)| a * b).sum())
+                .collect();
+
+            let result = solve_linear_system(&matrix, &rhs);
+            if determinant(&matrix) == 0 {
+                prop_assert_eq!(result, Err(LinearSystemError::Singular));
+            } else {
+                prop_assert_eq!(result, Ok(x));
+            }
+        }
+    }
+}
@@ -3,7 +3,11 @@ extern crate core;
 extern crate itertools;
 #[macro_use]
 extern crate text_io;
+mod answer_cache;
+mod blog_draft;
+#[cfg(feature = "network")]
 mod bootstrap_day;
+mod dashboard;
 mod day_1;
 mod day_10;
 mod day_11;
@@ -27,56 +31,358 @@ mod day_6;
 mod day_7;
 mod day_8;
 mod day_9;
+mod encrypted_inputs;
+mod figures;
 mod helpers;
+mod progress;
+mod repl;
+mod report;
+mod runner;
+mod stars;
+mod trace;
+mod watch;
 
-use bootstrap_day::bootstrap_day;
-use std::io::{self, Write};
-use std::time::Instant;
+use answer_cache::{AnswerCache, CACHE_PATH};
+use blog_draft::{draft_post, DraftOutcome};
+#[cfg(feature = "network")]
+use bootstrap_day::{
+    bootstrap_day, fetch_stars, import_personal_stats, submit_answer, sync_all, SubmissionOutcome,
+};
+use clap::Parser;
+use report::{write_report, OutputFormat, PartResult};
+use runner::{run_isolated, Day, RunOptions, Runner, DEFAULT_TIMEOUT};
+#[cfg(feature = "network")]
+use stars::{Stars, STARS_PATH};
+use std::fs::File;
+use std::io::{self, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// The event year, used in the site's URLs, the files scaffolded for a new day, and the dashboard's title
+pub const YEAR: u16 = 2024;
+
+/// Run my Advent of Code 2024 solutions
+#[derive(Parser, Debug)]
+struct Cli {
+    /// The day to run, 0 to run all days. Prompted for if not provided
+    day: Option<u8>,
+    /// Ignore cached answers and recalculate them
+    #[arg(long)]
+    force: bool,
+    /// Mark the answers from this run as confirmed correct by the puzzle site
+    #[arg(long)]
+    confirm: bool,
+    /// Rebuild, test and re-run the day whenever its source, fixtures or input change
+    #[arg(long, requires = "day")]
+    watch: bool,
+    /// When bootstrapping a day that hasn't unlocked yet, count down and wait for it to unlock
+    #[arg(long)]
+    wait: bool,
+    /// Draft the day's blog post from its docs and source, unless the post has been edited by hand
+    #[arg(long, requires = "day")]
+    draft_post: bool,
+    /// List where day 1's two lists disagree, as a table, or in the format given by `--format`
+    #[arg(long, requires = "day")]
+    reconcile: bool,
+    /// Solve day 1 by streaming this file through an external sort, for lists too big to fit in memory
+    #[arg(long, value_name = "PATH", requires = "day")]
+    stream: Option<PathBuf>,
+    /// Start an interactive session for running days, see `help` in the session for the commands
+    #[arg(long, conflicts_with = "day")]
+    repl: bool,
+    /// Show a full-screen view of all 25 days, from which days can be run or bootstrapped
+    #[arg(long, conflicts_with = "day")]
+    dashboard: bool,
+    /// Read the stars earned for each day from the calendar page, and list the days with any that look out of step
+    #[arg(long, conflicts_with = "day")]
+    stars: bool,
+    /// Download the inputs and puzzle pages for every unlocked day that are missing from `res/`
+    #[arg(long, conflicts_with = "day")]
+    sync: bool,
+    /// Import the completion times and ranks from the personal leaderboard page into the data for `pubs`
+    #[arg(long, conflicts_with = "day")]
+    import_stats: bool,
+    /// Draw the figures for the write-ups into `pubs/assets/images`, only those for the day if one is given
+    #[arg(long)]
+    figures: bool,
+    /// Encrypt the inputs in `res/` and their confirmed answers into `inputs/`, so that they can be committed
+    #[arg(long, conflicts_with = "day")]
+    encrypt_inputs: bool,
+    /// Re-run every day with an encrypted input, and check the answers against those confirmed for it
+    #[arg(long, conflicts_with = "day")]
+    check: bool,
+    /// Submit the answer to this part of the day to the puzzle site, confirming it in the cache if it is correct
+    #[arg(long, value_name = "PART", requires = "day", value_parser = clap::value_parser!(u8).range(1..=2))]
+    submit: Option<u8>,
+    /// The time limit for each day when running all days, days that are known to be slow have their own limit
+    #[arg(long, value_name = "SECONDS")]
+    timeout: Option<u64>,
+    /// How to output the answers
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+    /// Write trace events from the solutions at this level or above, e.g. `debug` or `trace`
+    #[arg(long, value_name = "LEVEL")]
+    trace: Option<tracing::Level>,
+    /// Only trace events from this day
+    #[arg(long, value_name = "DAY", requires = "trace")]
+    trace_day: Option<u8>,
+    /// Write trace events to this file as JSON lines, rather than to stderr
+    #[arg(long, value_name = "PATH", requires = "trace")]
+    trace_file: Option<PathBuf>,
+}
 
 fn main() {
-    print!("Which day? (0 to run all): ");
-    io::stdout().flush().unwrap();
-
-    let day: u8 = read!();
-    let days: Vec<Box<dyn Fn() -> ()>> = vec![
-        Box::new(|| day_1::run()),
-        Box::new(|| day_2::run()),
-        Box::new(|| day_3::run()),
-        Box::new(|| day_4::run()),
-        Box::new(|| day_5::run()),
-        Box::new(|| day_6::run()),
-        Box::new(|| day_7::run()),
-        Box::new(|| day_8::run()),
-        Box::new(|| day_9::run()),
-        Box::new(|| day_10::run()),
-        Box::new(|| day_11::run()),
-        Box::new(|| day_12::run()),
-        Box::new(|| day_13::run()),
-        Box::new(|| day_14::run()),
-        Box::new(|| day_15::run()),
-        Box::new(|| day_16::run()),
-        Box::new(|| day_17::run()),
-        Box::new(|| day_18::run()),
-        Box::new(|| day_19::run()),
-        Box::new(|| day_20::run()),
-        Box::new(|| day_21::run()),
-        Box::new(|| day_22::run()),
-        Box::new(|| day_23::run()),
+    let cli = Cli::parse();
+    trace::init(cli.trace, cli.trace_day, cli.trace_file.as_deref())
+        .expect("Failed to initialise tracing");
+    progress::init();
+
+    if cli.watch {
+        return watch::watch(cli.day.unwrap());
+    }
+
+    if cli.sync {
+        return sync();
+    }
+
+    if cli.import_stats {
+        return import_stats();
+    }
+
+    if cli.figures {
+        let day = cli.day.filter(|&day| day != 0);
+        let written =
+            figures::export_figures(Path::new("."), day).expect("Failed to write figures");
+        for path in written {
+            println!("Wrote {}", path.display());
+        }
+        return;
+    }
+
+    let days: Vec<Day> = vec![
+        Day::new(1, "1", day_1::run).with_params(&day_1::PARAMS),
+        Day::new(2, "2", day_2::run).with_params(&day_2::PARAMS),
+        Day::new(3, "1", day_3::run),
+        Day::new(4, "1", day_4::run),
+        Day::new(5, "1", day_5::run),
+        Day::new(6, "1", day_6::run).with_timeout(Duration::from_secs(120)),
+        Day::new(7, "1", day_7::run),
+        Day::new(8, "2", day_8::run),
+        Day::new(9, "1", day_9::run),
+        Day::new(10, "1", day_10::run),
+        Day::new(11, "1", day_11::run),
+        Day::new(12, "1", day_12::run),
+        Day::new(13, "1", day_13::run),
+        Day::new(14, "1", day_14::run).with_params(&day_14::PARAMS),
+        Day::new(15, "1", day_15::run),
+        Day::new(16, "1", day_16::run),
+        Day::new(17, "1", day_17::run),
+        Day::new(18, "1", day_18::run),
+        Day::new(19, "1", day_19::run),
+        Day::new(20, "1", day_20::run).with_params(&day_20::PARAMS),
+        Day::new(21, "1", day_21::run),
+        Day::new(22, "1", day_22::run),
+        Day::new(23, "1", day_23::run),
     ];
 
+    if cli.dashboard {
+        return dashboard::dashboard(&days);
+    }
+
+    if cli.stars {
+        update_stars();
+        return dashboard::print_table(&days);
+    }
+
+    if cli.check {
+        let passed = encrypted_inputs::check(&days, Path::new(".")).expect("Failed to check days");
+        std::process::exit(if passed { 0 } else { 1 });
+    }
+
+    let options = RunOptions {
+        force: cli.force,
+        confirm: cli.confirm,
+        format: cli.format,
+        ..RunOptions::default()
+    };
+    let text = options.format == OutputFormat::Text;
+    let timeout = cli.timeout.map_or(DEFAULT_TIMEOUT, Duration::from_secs);
+    let mut cache = AnswerCache::load(CACHE_PATH).expect("Failed to load answer cache");
+
+    if cli.encrypt_inputs {
+        let written = encrypted_inputs::encrypt_inputs(Path::new("."), &cache)
+            .expect("Failed to encrypt inputs");
+        return println!("Encrypted the inputs for days {written:?} into inputs/");
+    }
+
+    if cli.repl {
+        repl::repl(&days, options, &mut cache);
+        return cache.save().expect("Failed to save answer cache");
+    }
+
+    let day: u8 = cli.day.unwrap_or_else(|| {
+        print!("Which day? (0 to run all): ");
+        io::stdout().flush().unwrap();
+
+        read!()
+    });
+
+    if cli.draft_post {
+        return match draft_post(Path::new("."), day).expect("Failed to draft blog post") {
+            DraftOutcome::Written(path) => println!("Draft written to {}", path.display()),
+            DraftOutcome::EditedByHand(path) => {
+                println!(
+                    "{} has been edited by hand, not overwriting it",
+                    path.display()
+                )
+            }
+        };
+    }
+
+    if cli.reconcile {
+        if day != 1 {
+            return eprintln!("Only day 1 has a reconciliation report");
+        }
+        let input = Runner::new(&days[0], options, &mut cache).input();
+        return day_1::write_reconciliation(&input, options.format, io::stdout())
+            .expect("Failed to write reconciliation report");
+    }
+
+    if let Some(path) = cli.stream {
+        if day != 1 {
+            return eprintln!("Only day 1 can be streamed");
+        }
+        let file = File::open(&path).expect("Failed to open the input to stream");
+        let results = day_1::solve_streaming(
+            BufReader::new(file),
+            day_1::STREAM_CHUNK_SIZE,
+            options.format,
+        )
+        .expect("Failed to stream the input");
+        return write_report(&results, options.format, io::stdout())
+            .expect("Failed to write report");
+    }
+
     let start = Instant::now();
-    match day.checked_sub(1).and_then(|idx| days.get(idx as usize)) {
-        Some(solution) => solution(),
-        None if day == 0 => days.iter().enumerate().for_each(|(i, solution)| {
-            let start = Instant::now();
-            println!("==== Day {} ====", i + 1);
-            solution();
-            println!("-- took {:.2?}", start.elapsed());
-        }),
-        None if day >= 1 && day <= 25 => bootstrap_day(day).expect("Failed to bootstrap day"),
-        None => println!("Invalid Day {}", day),
+    let results: Vec<PartResult> = match day.checked_sub(1).and_then(|idx| days.get(idx as usize)) {
+        Some(solution) => Runner::new(solution, options, &mut cache).run(),
+        None if day == 0 => days
+            .iter()
+            .flat_map(|solution| {
+                let start = Instant::now();
+                if text {
+                    println!("==== Day {} ====", solution.day);
+                }
+                let results = run_isolated(solution, options, &mut cache, timeout);
+                if text {
+                    println!("-- took {:.2?}", start.elapsed());
+                }
+                results
+            })
+            .collect(),
+        None if day >= 1 && day <= 25 => {
+            bootstrap(day, cli.wait);
+            Vec::new()
+        }
+        None => {
+            println!("Invalid Day {}", day);
+            Vec::new()
+        }
+    };
+
+    if let Some(part) = cli.submit {
+        submit(&results, part, &mut cache);
     }
 
-    println!();
-    println!("Finished in {:.2?}", start.elapsed());
+    cache.save().expect("Failed to save answer cache");
+    write_report(&results, options.format, io::stdout()).expect("Failed to write report");
+
+    if text {
+        println!();
+        println!("Finished in {:.2?}", start.elapsed());
+    }
+}
+
+/// Explains why the features that use the puzzle site are missing
+#[cfg(not(feature = "network"))]
+const NETWORK_UNAVAILABLE: &str = "is unavailable as this was built without the `network` feature";
+
+/// Download the input for a day that hasn't been solved yet, and scaffold its files
+#[cfg(feature = "network")]
+fn bootstrap(day: u8, wait: bool) {
+    bootstrap_day(day, wait).expect("Failed to bootstrap day");
+}
+
+#[cfg(not(feature = "network"))]
+fn bootstrap(day: u8, _wait: bool) {
+    eprintln!("Day {day} has not been solved yet, and bootstrapping it {NETWORK_UNAVAILABLE}");
+}
+
+/// Download the missing inputs and puzzle pages for every unlocked day
+#[cfg(feature = "network")]
+fn sync() {
+    sync_all().expect("Failed to sync puzzles");
+}
+
+#[cfg(not(feature = "network"))]
+fn sync() {
+    eprintln!("Syncing puzzles {NETWORK_UNAVAILABLE}");
+}
+
+/// Save the personal leaderboard stats for `pubs` to render next to each write-up
+#[cfg(feature = "network")]
+fn import_stats() {
+    import_personal_stats().expect("Failed to import personal stats");
+}
+
+#[cfg(not(feature = "network"))]
+fn import_stats() {
+    eprintln!("Importing personal stats {NETWORK_UNAVAILABLE}");
+}
+
+/// Read the stars for each day from the site, and cache them
+#[cfg(feature = "network")]
+fn update_stars() {
+    let mut stars = Stars::load(STARS_PATH).expect("Failed to load stars");
+    fetch_stars(&mut stars).expect("Failed to fetch stars");
+}
+
+#[cfg(not(feature = "network"))]
+fn update_stars() {
+    eprintln!("Fetching stars {NETWORK_UNAVAILABLE}, showing the stars from the last time they were fetched");
+}
+
+#[cfg(not(feature = "network"))]
+fn submit(_results: &[PartResult], _part: u8, _cache: &mut AnswerCache) {
+    eprintln!("Submitting answers {NETWORK_UNAVAILABLE}");
+}
+
+/// Submit the answer for `part` from the results of the run, and confirm it in the cache if it was correct
+#[cfg(feature = "network")]
+fn submit(results: &[PartResult], part: u8, cache: &mut AnswerCache) {
+    let Some(result) = results.iter().find(|result| result.part == part) else {
+        eprintln!("There is no answer for part {part} to submit");
+        return;
+    };
+
+    match submit_answer(result.day, part, &result.answer) {
+        Ok(SubmissionOutcome::Correct) => {
+            println!("{} is the right answer", result.answer);
+            if let Some(key) = &result.cache_key {
+                cache.confirm(key);
+            }
+        }
+        Ok(SubmissionOutcome::Incorrect(hint)) => println!(
+            "{} is not the right answer{}",
+            result.answer,
+            hint.map(|hint| format!(", {hint}")).unwrap_or_default()
+        ),
+        Ok(SubmissionOutcome::TooSoon(message)) | Ok(SubmissionOutcome::Unknown(message)) => {
+            println!("{message}")
+        }
+        Ok(SubmissionOutcome::WrongLevel) => {
+            println!("Part {part} has already been completed, or is not unlocked yet")
+        }
+        Err(e) => eprintln!("Failed to submit answer: {e}"),
+    }
 }
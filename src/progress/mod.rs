@@ -0,0 +1,220 @@
+//! Progress reporting for solutions that take a long time, e.g. the obstruction search in [`crate::day_6`].
+//!
+//! A solver creates a [`Progress`] with the total amount of work, and calls [`Progress::add`] as it completes some.
+//! `Progress` only uses atomics, so it can be shared by reference with rayon workers. Updates are passed to the
+//! reporter installed with [`set_reporter`], at most every [`REPORT_INTERVAL`], and once more when the `Progress` is
+//! dropped. With no reporter installed, updates are discarded.
+//!
+//! [`init`] installs a reporter that draws a progress bar with an ETA on stderr, but only if stderr is a terminal, so
+//! that progress doesn't end up in redirected output, or the output captured by `--dashboard` and `--watch`.
+
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// The minimum time between updates passed to the reporter
+pub const REPORT_INTERVAL: Duration = Duration::from_millis(100);
+/// The width of the bar drawn by the terminal reporter, excluding the label and counts
+const BAR_WIDTH: usize = 30;
+
+/// A snapshot of a solver's progress
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct ProgressUpdate {
+    pub label: &'static str,
+    pub done: u64,
+    pub total: u64,
+    pub elapsed: Duration,
+    /// Set for the final update, when the work has finished or been abandoned
+    pub finished: bool,
+}
+
+impl ProgressUpdate {
+    /// The estimated time until all the work is done, assuming it continues at the same rate, or `None` if nothing
+    /// has been done yet to base an estimate on
+    pub fn eta(&self) -> Option<Duration> {
+        if self.done == 0 {
+            return None;
+        }
+
+        let remaining = self.total.saturating_sub(self.done) as f64 / self.done as f64;
+        Some(self.elapsed.mul_f64(remaining))
+    }
+}
+
+/// Something that is told about progress, e.g. to draw it
+type Reporter = Box<dyn Fn(ProgressUpdate) + Send + Sync>;
+
+static REPORTER: OnceLock<Reporter> = OnceLock::new();
+
+/// Install the reporter for all [`Progress`]. This can only be done once, later calls are ignored.
+pub fn set_reporter(reporter: impl Fn(ProgressUpdate) + Send + Sync + 'static) {
+    let _ = REPORTER.set(Box::new(reporter));
+}
+
+/// Install the terminal progress bar if stderr is a terminal, otherwise leave progress unreported
+pub fn init() {
+    if std::io::stderr().is_terminal() {
+        set_reporter(|update| {
+            let mut stderr = std::io::stderr().lock();
+            if update.finished {
+                let _ = write!(stderr, "\r\x1b[2K");
+            } else {
+                let _ = write!(stderr, "\r\x1b[2K{}", render_bar(&update));
+            }
+            let _ = stderr.flush();
+        });
+    }
+}
+
+/// Format a duration as minutes and seconds, e.g. `1:05`
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Draw an update as a single line, e.g. `day 6 [=========>          ] 45% 2500/5556 ETA 0:12`
+pub fn render_bar(update: &ProgressUpdate) -> String {
+    let fraction = if update.total == 0 {
+        1.0
+    } else {
+        (update.done as f64 / update.total as f64).min(1.0)
+    };
+    let filled = (fraction * BAR_WIDTH as f64) as usize;
+    let bar = if filled < BAR_WIDTH {
+        format!(
+            "{}>{}",
+            "=".repeat(filled),
+            " ".repeat(BAR_WIDTH - filled - 1)
+        )
+    } else {
+        "=".repeat(BAR_WIDTH)
+    };
+    let eta = update
+        .eta()
+        .map(format_duration)
+        .unwrap_or_else(|| "-:--".to_string());
+
+    format!(
+        "{} [{bar}] {:>3}% {}/{} ETA {eta}",
+        update.label,
+        (fraction * 100.0) as u32,
+        update.done,
+        update.total
+    )
+}
+
+/// A count of the work done towards a total, safe to update from several threads
+#[derive(Debug)]
+pub struct Progress {
+    label: &'static str,
+    total: u64,
+    done: AtomicU64,
+    started: Instant,
+    /// Milliseconds since `started` of the last update passed to the reporter
+    last_report: AtomicU64,
+}
+
+impl Progress {
+    /// Start tracking `total` units of work, labelled in the progress bar by `label`
+    pub fn new(label: &'static str, total: u64) -> Progress {
+        Progress {
+            label,
+            total,
+            done: AtomicU64::new(0),
+            started: Instant::now(),
+            last_report: AtomicU64::new(0),
+        }
+    }
+
+    /// The units of work completed so far
+    pub fn done(&self) -> u64 {
+        self.done.load(Ordering::Relaxed)
+    }
+
+    fn update(&self, done: u64, finished: bool) -> ProgressUpdate {
+        ProgressUpdate {
+            label: self.label,
+            done,
+            total: self.total,
+            elapsed: self.started.elapsed(),
+            finished,
+        }
+    }
+
+    /// Record `amount` more units of work as done, reporting it if it has been long enough since the last report.
+    /// Only the thread that wins the race to update `last_report` reports, so workers don't queue up on the reporter.
+    pub fn add(&self, amount: u64) {
+        let done = self.done.fetch_add(amount, Ordering::Relaxed) + amount;
+
+        let Some(reporter) = REPORTER.get() else {
+            return;
+        };
+        let now = self.started.elapsed().as_millis() as u64;
+        let last = self.last_report.load(Ordering::Relaxed);
+        if now >= last + REPORT_INTERVAL.as_millis() as u64
+            && self
+                .last_report
+                .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            reporter(self.update(done, false));
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if let Some(reporter) = REPORTER.get() {
+            reporter(self.update(self.done(), true));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::progress::*;
+    use rayon::prelude::*;
+
+    fn update(done: u64, total: u64, elapsed: u64) -> ProgressUpdate {
+        ProgressUpdate {
+            label: "day 6",
+            done,
+            total,
+            elapsed: Duration::from_secs(elapsed),
+            finished: false,
+        }
+    }
+
+    #[test]
+    fn can_estimate_time_remaining() {
+        assert_eq!(update(0, 100, 5).eta(), None);
+        assert_eq!(update(25, 100, 5).eta(), Some(Duration::from_secs(15)));
+        assert_eq!(update(100, 100, 5).eta(), Some(Duration::ZERO));
+        assert_eq!(update(120, 100, 5).eta(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn can_render_bar() {
+        assert_eq!(
+            render_bar(&update(0, 100, 0)),
+            "day 6 [>                             ]   0% 0/100 ETA -:--"
+        );
+        assert_eq!(
+            render_bar(&update(50, 100, 65)),
+            "day 6 [===============>              ]  50% 50/100 ETA 1:05"
+        );
+        assert_eq!(
+            render_bar(&update(100, 100, 65)),
+            "day 6 [==============================] 100% 100/100 ETA 0:00"
+        );
+    }
+
+    #[test]
+    fn can_count_progress_from_rayon_workers() {
+        let progress = Progress::new("test", 1000);
+        (0..1000).into_par_iter().for_each(|_| progress.add(1));
+
+        assert_eq!(progress.done(), 1000);
+    }
+}
@@ -0,0 +1,564 @@
+//! An interactive session for running days repeatedly without restarting the program, e.g. while trying out a
+//! solution against different inputs or parameters.
+//!
+//! Inputs are read once and kept for the session, so `reload` is needed to pick up edits to an input file. Answers
+//! using overridden parameters are not cached, see [`Runner::param`]. The commands entered are kept as a numbered
+//! history for the session, and can be repeated with `!N` or `!!`.
+
+use crate::answer_cache::AnswerCache;
+use crate::report::PartResult;
+use crate::runner::{Day, Params, RunOptions, Runner};
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+
+const HELP: &str = "Commands:
+  run <day> [part]          Run a day, or a single part of it
+  input <day> [path]        Use the input at path for a day, or go back to the default input
+  reload [day]              Re-read the inputs that have been loaded, or just the one for day
+  set <day> <name> <value>  Override one of a day's parameters
+  unset <day> <name>        Go back to the default for a parameter
+  params <day>              List a day's parameters and any overrides
+  timing [on|off]           Toggle showing how long each part takes
+  history                   List the commands entered this session
+  !N, !!                    Repeat command N from the history, or the last command
+  help                      Show this message
+  quit, exit                End the session";
+
+/// A parsed line of input
+#[derive(Eq, PartialEq, Debug, Clone)]
+enum Command {
+    Run {
+        day: u8,
+        part: Option<u8>,
+    },
+    Input {
+        day: u8,
+        path: Option<PathBuf>,
+    },
+    Reload(Option<u8>),
+    Set {
+        day: u8,
+        name: String,
+        value: String,
+    },
+    Unset {
+        day: u8,
+        name: String,
+    },
+    Params(u8),
+    Timing(Option<bool>),
+    History,
+    /// Repeat the command at this 1-based position in the history
+    Repeat(usize),
+    RepeatLast,
+    Help,
+    Quit,
+}
+
+fn parse_number<T: std::str::FromStr>(word: Option<&str>, what: &str) -> Result<T, String> {
+    let word = word.ok_or(format!("Expected {what}"))?;
+    word.parse().map_err(|_| format!("Invalid {what}: {word}"))
+}
+
+fn parse_command(line: &str) -> Result<Command, String> {
+    let mut words = line.split_whitespace();
+    let name = words.next().unwrap_or("");
+
+    let command = match name {
+        "run" => Command::Run {
+            day: parse_number(words.next(), "day")?,
+            part: words
+                .next()
+                .map(|part| parse_number(Some(part), "part"))
+                .transpose()?,
+        },
+        "input" => Command::Input {
+            day: parse_number(words.next(), "day")?,
+            path: words.next().map(PathBuf::from),
+        },
+        "reload" => Command::Reload(
+            words
+                .next()
+                .map(|day| parse_number(Some(day), "day"))
+                .transpose()?,
+        ),
+        "set" => Command::Set {
+            day: parse_number(words.next(), "day")?,
+            name: words.next().ok_or("Expected a parameter name")?.to_string(),
+            value: words.next().ok_or("Expected a value")?.to_string(),
+        },
+        "unset" => Command::Unset {
+            day: parse_number(words.next(), "day")?,
+            name: words.next().ok_or("Expected a parameter name")?.to_string(),
+        },
+        "params" => Command::Params(parse_number(words.next(), "day")?),
+        "timing" => Command::Timing(match words.next() {
+            None => None,
+            Some("on") => Some(true),
+            Some("off") => Some(false),
+            Some(other) => return Err(format!("Expected on or off: {other}")),
+        }),
+        "history" => Command::History,
+        "!!" => Command::RepeatLast,
+        _ if name.starts_with('!') => {
+            Command::Repeat(parse_number(Some(&name[1..]), "history number")?)
+        }
+        "help" => Command::Help,
+        "quit" | "exit" => Command::Quit,
+        _ => return Err(format!("Unknown command: {name}, try help")),
+    };
+
+    match words.next() {
+        Some(extra) => Err(format!("Unexpected argument: {extra}")),
+        None => Ok(command),
+    }
+}
+
+/// An input loaded into the session
+struct LoadedInput {
+    path: PathBuf,
+    contents: String,
+}
+
+/// The state kept between commands
+struct Session<'a> {
+    days: &'a [Day],
+    options: RunOptions,
+    inputs: BTreeMap<u8, LoadedInput>,
+    params: BTreeMap<u8, Params>,
+    history: Vec<String>,
+}
+
+impl<'a> Session<'a> {
+    fn new(days: &'a [Day], options: RunOptions) -> Session<'a> {
+        Session {
+            days,
+            options,
+            inputs: BTreeMap::new(),
+            params: BTreeMap::new(),
+            history: Vec::new(),
+        }
+    }
+
+    fn day(&self, day: u8) -> Result<&'a Day, String> {
+        self.days
+            .iter()
+            .find(|solution| solution.day == day)
+            .ok_or(format!("Day {day} has not been solved"))
+    }
+
+    fn load_input(&mut self, day: u8, path: PathBuf) -> Result<&str, String> {
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        self.inputs.insert(day, LoadedInput { path, contents });
+
+        Ok(&self.inputs[&day].contents)
+    }
+
+    /// The input for `day`, loading the default input if it hasn't been loaded yet
+    fn input(&mut self, day: u8) -> Result<String, String> {
+        if let Some(input) = self.inputs.get(&day) {
+            return Ok(input.contents.clone());
+        }
+
+        let path = PathBuf::from(format!("res/day-{day}-input.txt"));
+        self.load_input(day, path)
+            .map(|contents| contents.to_string())
+    }
+
+    fn run(
+        &mut self,
+        day: u8,
+        part: Option<u8>,
+        cache: &mut AnswerCache,
+    ) -> Result<Vec<PartResult>, String> {
+        let solution = self.day(day)?;
+        let input = self.input(day)?;
+        let params = self.params.get(&day).cloned().unwrap_or_default();
+        let options = RunOptions {
+            part,
+            ..self.options
+        };
+
+        let results = catch_unwind(AssertUnwindSafe(|| {
+            Runner::new(solution, options, cache)
+                .with_input(input)
+                .with_params(params)
+                .run()
+        }))
+        .map_err(|_| format!("Day {day} panicked"))?;
+
+        match part {
+            Some(part) if results.is_empty() => Err(format!("Day {day} has no part {part}")),
+            _ => Ok(results),
+        }
+    }
+
+    /// List a day's parameters, with the value of any that have been overridden
+    fn describe_params(&self, day: u8) -> Result<String, String> {
+        let solution = self.day(day)?;
+        if solution.params.is_empty() {
+            return Ok(format!("Day {day} has no parameters"));
+        }
+
+        let overrides = self.params.get(&day);
+        Ok(solution
+            .params
+            .iter()
+            .map(|param| {
+                let value = overrides
+                    .and_then(|params| params.get(param.name))
+                    .map(|value| format!(" = {value}"))
+                    .unwrap_or_default();
+                format!("  {}{value}: {}", param.name, param.description)
+            })
+            .join("\n"))
+    }
+
+    /// Carry out one command, returning whether the session should continue
+    fn execute(&mut self, command: Command, cache: &mut AnswerCache) -> Result<bool, String> {
+        match command {
+            Command::Run { day, part } => {
+                self.run(day, part, cache)?;
+            }
+            Command::Input { day, path } => {
+                self.day(day)?;
+                match path {
+                    Some(path) => {
+                        self.load_input(day, path)?;
+                    }
+                    None => {
+                        self.inputs.remove(&day);
+                    }
+                }
+                self.input(day)?;
+                println!("Day {day} is using {}", self.inputs[&day].path.display());
+            }
+            Command::Reload(day) => {
+                let days: Vec<u8> = match day {
+                    Some(day) => vec![day],
+                    None => self.inputs.keys().copied().collect(),
+                };
+                for day in days {
+                    let path = self
+                        .inputs
+                        .get(&day)
+                        .map(|input| input.path.clone())
+                        .ok_or(format!("The input for day {day} has not been loaded"))?;
+                    self.load_input(day, path.clone())?;
+                    println!("Reloaded {}", path.display());
+                }
+            }
+            Command::Set { day, name, value } => {
+                if self.day(day)?.param(&name).is_none() {
+                    return Err(format!(
+                        "Day {day} has no parameter {name}, see params {day}"
+                    ));
+                }
+                self.params.entry(day).or_default().insert(name, value);
+            }
+            Command::Unset { day, name } => {
+                self.params.entry(day).or_default().remove(&name);
+            }
+            Command::Params(day) => println!("{}", self.describe_params(day)?),
+            Command::Timing(timing) => {
+                self.options.timing = timing.unwrap_or(!self.options.timing);
+                println!(
+                    "Timing is {}",
+                    if self.options.timing { "on" } else { "off" }
+                );
+            }
+            Command::History => {
+                for (i, line) in self.history.iter().enumerate() {
+                    println!("{:>4}  {line}", i + 1);
+                }
+            }
+            Command::Repeat(_) | Command::RepeatLast => {
+                unreachable!("repeats are expanded before executing")
+            }
+            Command::Help => println!("{HELP}"),
+            Command::Quit => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    /// Parse and carry out a line of input, recording it in the history. Repeats are replaced by the line they repeat.
+    fn handle_line(&mut self, line: &str, cache: &mut AnswerCache) -> Result<bool, String> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(true);
+        }
+
+        let line = match parse_command(line)? {
+            Command::Repeat(n) => n
+                .checked_sub(1)
+                .and_then(|i| self.history.get(i))
+                .ok_or(format!("There is no command {n} in the history"))?
+                .clone(),
+            Command::RepeatLast => self.history.last().ok_or("The history is empty")?.clone(),
+            _ => line.to_string(),
+        };
+
+        self.history.push(line.clone());
+        self.execute(parse_command(&line)?, cache)
+    }
+}
+
+/// Read commands from stdin until `quit` or the end of input
+pub fn repl(days: &[Day], options: RunOptions, cache: &mut AnswerCache) {
+    let mut session = Session::new(days, options);
+    let mut lines = io::stdin().lock().lines();
+    println!("Type help for a list of commands");
+
+    loop {
+        print!("> ");
+        io::stdout().flush().unwrap();
+
+        let Some(Ok(line)) = lines.next() else {
+            break;
+        };
+        match session.handle_line(&line, cache) {
+            Ok(true) => (),
+            Ok(false) => break,
+            Err(message) => eprintln!("{message}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::*;
+    use crate::runner::Param;
+    use std::env::temp_dir;
+
+    const PARAMS: [Param; 1] = [Param {
+        name: "factor",
+        description: "Multiplies the answer (default 1)",
+    }];
+
+    fn example_days() -> Vec<Day> {
+        vec![
+            Day::new(1, "1", |runner| {
+                let input = runner.input();
+                let factor = runner.param("factor", 1);
+                runner.part(1, "Length: {}", || input.len() * factor);
+                runner.part(2, "Lines: {}", || input.lines().count());
+            })
+            .with_params(&PARAMS),
+            Day::new(2, "1", |_| panic!("Not solved")),
+        ]
+    }
+
+    #[test]
+    fn can_parse_commands() {
+        assert_eq!(
+            parse_command("run 1"),
+            Ok(Command::Run { day: 1, part: None })
+        );
+        assert_eq!(
+            parse_command("  run 14 2 "),
+            Ok(Command::Run {
+                day: 14,
+                part: Some(2)
+            })
+        );
+        assert_eq!(
+            parse_command("input 1 res/day-1-example.txt"),
+            Ok(Command::Input {
+                day: 1,
+                path: Some(PathBuf::from("res/day-1-example.txt"))
+            })
+        );
+        assert_eq!(parse_command("reload"), Ok(Command::Reload(None)));
+        assert_eq!(
+            parse_command("set 20 threshold 50"),
+            Ok(Command::Set {
+                day: 20,
+                name: "threshold".to_string(),
+                value: "50".to_string()
+            })
+        );
+        assert_eq!(
+            parse_command("timing off"),
+            Ok(Command::Timing(Some(false)))
+        );
+        assert_eq!(parse_command("!3"), Ok(Command::Repeat(3)));
+        assert_eq!(parse_command("!!"), Ok(Command::RepeatLast));
+        assert_eq!(parse_command("exit"), Ok(Command::Quit));
+
+        assert_eq!(parse_command("run"), Err("Expected day".to_string()));
+        assert_eq!(parse_command("run x"), Err("Invalid day: x".to_string()));
+        assert_eq!(
+            parse_command("run 1 2 3"),
+            Err("Unexpected argument: 3".to_string())
+        );
+        assert_eq!(
+            parse_command("jump"),
+            Err("Unknown command: jump, try help".to_string())
+        );
+    }
+
+    fn write_input(name: &str, contents: &str) -> PathBuf {
+        let path = temp_dir().join(format!("aoc-repl-{name}-{}.txt", std::process::id()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    /// Run a line, returning the answers from the cache entries made by running it
+    fn answers(session: &mut Session, line: &str) -> Vec<(u8, String)> {
+        let mut cache = AnswerCache::new("unused.json");
+        session.handle_line(line, &mut cache).unwrap();
+
+        let day = session.day(1).unwrap();
+        let input = session.input(1).unwrap();
+        (1..=2)
+            .flat_map(|part| {
+                let key = crate::answer_cache::CacheKey::new(1, part, &input, day.version);
+                cache.get(&key).map(|cached| (part, cached.answer.clone()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn can_run_with_alternative_input() {
+        let days = example_days();
+        let mut session = Session::new(&days, RunOptions::default());
+        let mut cache = AnswerCache::new("unused.json");
+        let path = write_input("alternative", "ab\ncd");
+
+        session
+            .handle_line(&format!("input 1 {}", path.display()), &mut cache)
+            .unwrap();
+        assert_eq!(
+            answers(&mut session, "run 1"),
+            vec![(1, "5".to_string()), (2, "2".to_string())]
+        );
+        assert_eq!(answers(&mut session, "run 1 2"), vec![(2, "2".to_string())]);
+
+        fs::write(&path, "abc").unwrap();
+        assert_eq!(answers(&mut session, "run 1 1"), vec![(1, "5".to_string())]);
+        session.handle_line("reload", &mut cache).unwrap();
+        assert_eq!(answers(&mut session, "run 1 1"), vec![(1, "3".to_string())]);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    /// Run a `run` command, returning the answers it gives whether or not they were cached
+    fn run_answers(session: &mut Session, line: &str) -> Vec<(u8, String)> {
+        let Ok(Command::Run { day, part }) = parse_command(line) else {
+            panic!("{line} is not a run command");
+        };
+        let mut cache = AnswerCache::new("unused.json");
+
+        session
+            .run(day, part, &mut cache)
+            .unwrap()
+            .into_iter()
+            .map(|result| (result.part, result.answer))
+            .collect()
+    }
+
+    #[test]
+    fn can_override_params() {
+        let days = example_days();
+        let mut session = Session::new(&days, RunOptions::default());
+        let mut cache = AnswerCache::new("unused.json");
+        let path = write_input("params", "abcd");
+        session
+            .handle_line(&format!("input 1 {}", path.display()), &mut cache)
+            .unwrap();
+
+        assert_eq!(
+            session.handle_line("set 1 speed 3", &mut cache),
+            Err("Day 1 has no parameter speed, see params 1".to_string())
+        );
+
+        session.handle_line("set 1 factor 3", &mut cache).unwrap();
+        assert_eq!(session.handle_line("params 1", &mut cache), Ok(true));
+        assert_eq!(
+            session.describe_params(1),
+            Ok("  factor = 3: Multiplies the answer (default 1)".to_string())
+        );
+        assert_eq!(
+            run_answers(&mut session, "run 1 1"),
+            vec![(1, "12".to_string())]
+        );
+
+        // Overridden answers are not cached
+        assert_eq!(answers(&mut session, "run 1 1"), vec![]);
+
+        session.handle_line("unset 1 factor", &mut cache).unwrap();
+        assert_eq!(
+            session.describe_params(1),
+            Ok("  factor: Multiplies the answer (default 1)".to_string())
+        );
+        assert_eq!(
+            run_answers(&mut session, "run 1 1"),
+            vec![(1, "4".to_string())]
+        );
+        assert_eq!(answers(&mut session, "run 1 1"), vec![(1, "4".to_string())]);
+        assert_eq!(
+            session.describe_params(2),
+            Ok("Day 2 has no parameters".to_string())
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn can_repeat_commands_from_history() {
+        let days = example_days();
+        let mut session = Session::new(&days, RunOptions::default());
+        let mut cache = AnswerCache::new("unused.json");
+
+        session.handle_line("timing", &mut cache).unwrap();
+        session.handle_line("params 1", &mut cache).unwrap();
+        session.handle_line("!1", &mut cache).unwrap();
+        assert!(!session.options.timing);
+        session.handle_line("!!", &mut cache).unwrap();
+        assert!(session.options.timing);
+
+        assert_eq!(
+            session.history,
+            vec!["timing", "params 1", "timing", "timing"]
+        );
+        assert_eq!(
+            session.handle_line("!9", &mut cache),
+            Err("There is no command 9 in the history".to_string())
+        );
+        assert_eq!(session.handle_line("quit", &mut cache), Ok(false));
+    }
+
+    #[test]
+    fn reports_errors_without_ending_session() {
+        let days = example_days();
+        let mut session = Session::new(&days, RunOptions::default());
+        let mut cache = AnswerCache::new("unused.json");
+        let path = write_input("errors", "abc");
+
+        assert_eq!(
+            session.handle_line("run 3", &mut cache),
+            Err("Day 3 has not been solved".to_string())
+        );
+        assert!(session
+            .handle_line("input 1 /does/not/exist.txt", &mut cache)
+            .unwrap_err()
+            .starts_with("Failed to read /does/not/exist.txt"));
+
+        session
+            .handle_line(&format!("input 2 {}", path.display()), &mut cache)
+            .unwrap();
+        assert_eq!(
+            session.handle_line("run 2", &mut cache),
+            Err("Day 2 panicked".to_string())
+        );
+        assert_eq!(session.handle_line("help", &mut cache), Ok(true));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
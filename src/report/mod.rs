@@ -0,0 +1,143 @@
+//! Machine-readable output of a run. Each part that is run produces a [`PartResult`], and [`write_report`] writes them
+//! in the requested [`OutputFormat`], so that scripts and the site generator don't need to parse each day's wording.
+
+use crate::answer_cache::CacheKey;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::io::Write;
+use std::time::Duration;
+
+/// How the results of a run should be output
+#[derive(Eq, PartialEq, Debug, Default, Copy, Clone, ValueEnum)]
+pub enum OutputFormat {
+    /// Each day's own description of its answers
+    #[default]
+    Text,
+    /// A JSON array with one record per day and part
+    Json,
+    /// A CSV file with a header row, and one row per day and part
+    Csv,
+}
+
+/// How an answer was arrived at
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// The solution was run to get the answer
+    Solved,
+    /// The answer was taken from the [`crate::answer_cache::AnswerCache`]
+    Cached,
+    /// The day ran past its time limit, so there is no answer
+    TimedOut,
+    /// The day panicked, so there is no answer
+    Panicked,
+}
+
+/// The outcome of running one part of one day
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct PartResult {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    #[serde(rename = "duration_ms", serialize_with = "serialize_millis")]
+    pub duration: Duration,
+    pub status: Status,
+    /// Used to look up the answer in the cache, e.g. to confirm it after submitting
+    #[serde(skip)]
+    pub cache_key: Option<CacheKey>,
+}
+
+/// Durations are output as fractional milliseconds, which is more convenient for scripts than serde's default of an
+/// object with seconds and nanoseconds.
+fn serialize_millis<S: serde::Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+}
+
+/// Write the results in a machine-readable format. Nothing is written for [`OutputFormat::Text`] as the answers have
+/// already been printed as the days ran.
+pub fn write_report(
+    results: &[PartResult],
+    format: OutputFormat,
+    writer: impl Write,
+) -> std::io::Result<()> {
+    match format {
+        OutputFormat::Text => Ok(()),
+        OutputFormat::Json => {
+            let mut writer = writer;
+            serde_json::to_writer_pretty(&mut writer, results)?;
+            writeln!(writer)
+        }
+        OutputFormat::Csv => {
+            let mut csv = csv::Writer::from_writer(writer);
+            for result in results {
+                csv.serialize(result)?;
+            }
+            csv.flush()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::*;
+
+    fn example_results() -> Vec<PartResult> {
+        vec![
+            PartResult {
+                day: 17,
+                part: 1,
+                answer: "4,6,3,5,6,3,5,2,1,0".to_string(),
+                duration: Duration::from_micros(1500),
+                status: Status::Solved,
+                cache_key: None,
+            },
+            PartResult {
+                day: 17,
+                part: 2,
+                answer: "117440".to_string(),
+                duration: Duration::ZERO,
+                status: Status::Cached,
+                cache_key: None,
+            },
+        ]
+    }
+
+    fn report_string(format: OutputFormat) -> String {
+        let mut buffer = Vec::new();
+        write_report(&example_results(), format, &mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn can_write_text_report() {
+        assert_eq!(report_string(OutputFormat::Text), "");
+    }
+
+    #[test]
+    fn can_write_json_report() {
+        let json: serde_json::Value =
+            serde_json::from_str(&report_string(OutputFormat::Json)).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"day": 17, "part": 1, "answer": "4,6,3,5,6,3,5,2,1,0", "duration_ms": 1.5, "status": "solved"},
+                {"day": 17, "part": 2, "answer": "117440", "duration_ms": 0.0, "status": "cached"},
+            ])
+        );
+    }
+
+    #[test]
+    fn can_write_csv_report() {
+        assert_eq!(
+            report_string(OutputFormat::Csv),
+            "day,part,answer,duration_ms,status
+17,1,\"4,6,3,5,6,3,5,2,1,0\",1.5,solved
+17,2,117440,0.0,cached
+"
+        );
+    }
+}
@@ -0,0 +1,582 @@
+//! Shared plumbing for running a day's solutions. Each day is registered in [`crate::main`] as a [`Day`], and its
+//! `run` function is passed a [`Runner`] that provides the puzzle input and reports the answer to each part.
+//!
+//! Routing the answers through the [`Runner`] rather than each day printing them directly allows cached answers to be
+//! returned from the [`AnswerCache`] without re-running slow solutions.
+
+use crate::answer_cache::{AnswerCache, CacheKey};
+use crate::encrypted_inputs::decrypt_input;
+use crate::report::{OutputFormat, PartResult, Status};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{debug, info_span};
+
+/// A value a day's `run` function reads with [`Runner::param`], so that it can be changed without recompiling
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Param {
+    pub name: &'static str,
+    /// What the parameter controls, including its default
+    pub description: &'static str,
+}
+
+/// Overridden values for a day's [`Param`]s, by name
+pub type Params = BTreeMap<String, String>;
+
+/// How long a day can run for in [`run_isolated`], unless it sets its own limit with [`Day::with_timeout`]
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A day's entry in the registry of solutions
+#[derive(Copy, Clone)]
+pub struct Day {
+    pub day: u8,
+    /// Bump this when the solution changes in a way that could change its answers, to invalidate cached answers
+    pub version: &'static str,
+    pub run: fn(&mut Runner),
+    pub params: &'static [Param],
+    /// Overrides the default time limit for slow days
+    pub timeout: Option<Duration>,
+}
+
+impl Day {
+    pub fn new(day: u8, version: &'static str, run: fn(&mut Runner)) -> Day {
+        Day {
+            day,
+            version,
+            run,
+            params: &[],
+            timeout: None,
+        }
+    }
+
+    /// Declare the parameters the day reads with [`Runner::param`]
+    pub fn with_params(self, params: &'static [Param]) -> Day {
+        Day { params, ..self }
+    }
+
+    pub fn param(&self, name: &str) -> Option<&Param> {
+        self.params.iter().find(|param| param.name == name)
+    }
+
+    /// Allow the day longer than the default time limit in [`run_isolated`]
+    pub fn with_timeout(self, timeout: Duration) -> Day {
+        Day {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+}
+
+/// Options that apply to all days in a run
+#[derive(Eq, PartialEq, Debug, Default, Copy, Clone)]
+pub struct RunOptions {
+    /// Ignore cached answers, recalculating and re-caching them
+    pub force: bool,
+    /// Mark the answers from this run as confirmed correct
+    pub confirm: bool,
+    /// Only [`OutputFormat::Text`] prints the answers as they are found
+    pub format: OutputFormat,
+    /// Only run this part, skipping the others
+    pub part: Option<u8>,
+    /// Print how long each part took alongside its answer
+    pub timing: bool,
+}
+
+/// A part's result, and the day's cache once the answer has been recorded in it
+type PartUpdate = (PartResult, AnswerCache);
+
+/// Passed to a day's `run` function to provide its input and collect its answers
+pub struct Runner<'a> {
+    day: &'a Day,
+    options: RunOptions,
+    cache: &'a mut AnswerCache,
+    input: Option<String>,
+    params: Params,
+    /// Set once a parameter has been overridden, as the answers are then not comparable with the cached answers
+    params_overridden: bool,
+    results: Vec<PartResult>,
+    /// Where [`run_isolated`] is sent each part as it finishes, so the finished parts aren't lost if a later one fails
+    updates: Option<Sender<PartUpdate>>,
+}
+
+impl<'a> Runner<'a> {
+    pub fn new(day: &'a Day, options: RunOptions, cache: &'a mut AnswerCache) -> Runner<'a> {
+        Runner {
+            day,
+            options,
+            cache,
+            input: None,
+            params: Params::new(),
+            params_overridden: false,
+            results: Vec::new(),
+            updates: None,
+        }
+    }
+
+    /// Use `input` as the puzzle input, rather than reading it from the default path
+    pub fn with_input(self, input: String) -> Runner<'a> {
+        Runner {
+            input: Some(input),
+            ..self
+        }
+    }
+
+    /// Override the defaults of the day's parameters
+    pub fn with_params(self, params: Params) -> Runner<'a> {
+        Runner { params, ..self }
+    }
+
+    /// Run the registered day's solutions with this runner, returning the result for each part
+    pub fn run(mut self) -> Vec<PartResult> {
+        let _span = info_span!("day", day = self.day.day).entered();
+        (self.day.run)(&mut self);
+
+        self.results
+    }
+
+    /// Read the puzzle input, expected to be at `<project_root>/res/day-N-input.txt`, unless it was provided with
+    /// [`Runner::with_input`]. If it is missing, the encrypted copy is used if the key is available, see
+    /// [`crate::encrypted_inputs`].
+    pub fn input(&mut self) -> String {
+        if let Some(input) = &self.input {
+            return input.clone();
+        }
+
+        let input = fs::read_to_string(format!("res/day-{}-input.txt", self.day.day))
+            .ok()
+            .or_else(|| decrypt_input(Path::new("."), self.day.day))
+            .expect("Failed to read file, and there is no encrypted input that can be decrypted");
+        self.input = Some(input.clone());
+
+        input
+    }
+
+    /// The value of a parameter, `default` unless it has been overridden. Answers that use an overridden parameter are
+    /// not cached.
+    pub fn param<T: FromStr>(&mut self, name: &'static str, default: T) -> T {
+        debug_assert!(
+            self.day.param(name).is_some(),
+            "{name} should be declared with Day::with_params"
+        );
+
+        match self.params.get(name).map(|value| value.parse()) {
+            Some(Ok(value)) => {
+                self.params_overridden = true;
+                value
+            }
+            Some(Err(_)) => {
+                eprintln!("Warning: invalid value for {name}, using the default");
+                default
+            }
+            None => default,
+        }
+    }
+
+    /// Report the answer to one part of the puzzle. `label` should contain a `{}` placeholder for the answer.
+    ///
+    /// If there is a cached answer for the current input, and the run is not forced, `solve` is not called, and the
+    /// cached answer is used instead.
+    pub fn part<T: Display>(&mut self, part: u8, label: &str, solve: impl FnOnce() -> T) {
+        if self.options.part.is_some_and(|only| only != part) {
+            return;
+        }
+
+        let _span = info_span!("part", day = self.day.day, part).entered();
+        let key = self
+            .input
+            .as_ref()
+            .filter(|_| !self.params_overridden)
+            .map(|input| CacheKey::new(self.day.day, part, input, self.day.version));
+
+        let cached = key
+            .as_ref()
+            .filter(|_| !self.options.force)
+            .and_then(|key| self.cache.get(key))
+            .map(|cached| cached.answer.clone());
+
+        let start = Instant::now();
+        let (answer, status) = match cached {
+            Some(answer) => {
+                debug!(%answer, "using cached answer");
+                (answer, Status::Cached)
+            }
+            None => (solve().to_string(), Status::Solved),
+        };
+        let duration = start.elapsed();
+
+        if self.options.format == OutputFormat::Text {
+            let suffix = if status == Status::Cached {
+                " (cached)"
+            } else {
+                ""
+            };
+            let timing = if self.options.timing {
+                format!(" [{duration:.2?}]")
+            } else {
+                String::new()
+            };
+            println!("{}{suffix}{timing}", label.replacen("{}", &answer, 1));
+        }
+
+        if let Some(key) = &key {
+            self.cache
+                .warnings(key, &answer)
+                .iter()
+                .for_each(|warning| eprintln!("Warning: {warning}"));
+
+            self.cache.insert(key.clone(), answer.clone());
+            if status == Status::Solved {
+                self.cache.record_duration(key, duration);
+            }
+            if self.options.confirm {
+                self.cache.confirm(key);
+            }
+        }
+
+        let result = PartResult {
+            day: self.day.day,
+            part,
+            answer,
+            duration,
+            status,
+            cache_key: key,
+        };
+        if let Some(updates) = &self.updates {
+            // The receiver is gone if the day timed out
+            let _ = updates.send((result.clone(), self.cache.clone()));
+        }
+        self.results.push(result);
+    }
+}
+
+/// The results recorded for each part that hadn't finished when a day failed
+fn failed_results(
+    day: &Day,
+    options: RunOptions,
+    finished: &[PartResult],
+    status: Status,
+    duration: Duration,
+) -> Vec<PartResult> {
+    (1..=2)
+        .filter(|&part| options.part.is_none_or(|only| only == part))
+        .filter(|&part| !finished.iter().any(|result| result.part == part))
+        .map(|part| PartResult {
+            day: day.day,
+            part,
+            answer: String::new(),
+            duration,
+            status,
+            cache_key: None,
+        })
+        .collect()
+}
+
+/// Run a day on its own thread, so that a panic, or running for longer than its time limit, is recorded as the day's
+/// [`Status`] rather than ending the whole run. Threads can't be killed, so a day that times out is left running in the
+/// background until the process exits.
+///
+/// The day is given a copy of the cache, which replaces `cache` as each part finishes. If the day fails, the parts that
+/// had already finished keep their answers, and only the rest are recorded as failed.
+pub fn run_isolated(
+    day: &Day,
+    options: RunOptions,
+    cache: &mut AnswerCache,
+    default_timeout: Duration,
+) -> Vec<PartResult> {
+    run_isolated_with_input(day, None, options, cache, default_timeout)
+}
+
+/// [`run_isolated`], using `input` as the puzzle input if it is provided, see [`Runner::with_input`]
+pub fn run_isolated_with_input(
+    day: &Day,
+    input: Option<String>,
+    options: RunOptions,
+    cache: &mut AnswerCache,
+    default_timeout: Duration,
+) -> Vec<PartResult> {
+    let solution = *day;
+    let mut day_cache = cache.clone();
+    let (sender, receiver) = mpsc::channel();
+
+    let start = Instant::now();
+    let handle = thread::Builder::new()
+        .name(format!("day-{}", day.day))
+        .spawn(move || {
+            let mut runner = Runner::new(&solution, options, &mut day_cache);
+            runner.updates = Some(sender);
+            match input {
+                Some(input) => runner.with_input(input),
+                None => runner,
+            }
+            .run();
+        })
+        .expect("Failed to start thread");
+
+    let timeout = day.timeout.unwrap_or(default_timeout);
+    let deadline = start + timeout;
+    let mut results = Vec::new();
+    let status = loop {
+        match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok((result, day_cache)) => {
+                results.push(result);
+                *cache = day_cache;
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                eprintln!("Day {} timed out after {timeout:.2?}", day.day);
+                break Status::TimedOut;
+            }
+            // The thread has ended, so this doesn't block
+            Err(RecvTimeoutError::Disconnected) => match handle.join() {
+                Ok(()) => return results,
+                Err(_) => {
+                    eprintln!("Day {} panicked", day.day);
+                    break Status::Panicked;
+                }
+            },
+        }
+    };
+
+    let failed = failed_results(day, options, &results, status, start.elapsed());
+    results.extend(failed);
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer_cache::{AnswerCache, CacheKey};
+    use crate::report::Status;
+    use crate::runner::*;
+
+    fn example_day() -> Day {
+        Day::new(1, "1", |runner| {
+            runner.input = Some("3   4".to_string());
+            runner.part(1, "Part 1: {}", || 11);
+            runner.part(2, "Part 2: {}", || -> u32 {
+                panic!("Should use cached value")
+            });
+        })
+    }
+
+    #[test]
+    fn can_use_cached_answers() {
+        let day = example_day();
+        let mut cache = AnswerCache::new("unused.json");
+        cache.insert(CacheKey::new(1, 2, "3   4", "1"), "31".to_string());
+
+        let results = Runner::new(&day, RunOptions::default(), &mut cache).run();
+
+        assert_eq!(
+            results
+                .iter()
+                .map(|result| (result.part, result.answer.as_str(), result.status))
+                .collect::<Vec<_>>(),
+            vec![(1, "11", Status::Solved), (2, "31", Status::Cached)]
+        );
+        assert_eq!(
+            cache
+                .get(&CacheKey::new(1, 1, "3   4", "1"))
+                .map(|cached| cached.answer.as_str()),
+            Some("11")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Should use cached value")]
+    fn can_force_recalculation() {
+        let day = example_day();
+        let mut cache = AnswerCache::new("unused.json");
+        cache.insert(CacheKey::new(1, 2, "3   4", "1"), "31".to_string());
+
+        let options = RunOptions {
+            force: true,
+            ..RunOptions::default()
+        };
+        Runner::new(&day, options, &mut cache).run();
+    }
+
+    #[test]
+    fn can_confirm_answers() {
+        let day = Day::new(1, "1", |runner| {
+            runner.input = Some("3   4".to_string());
+            runner.part(1, "Part 1: {}", || 11);
+        });
+        let mut cache = AnswerCache::new("unused.json");
+        let options = RunOptions {
+            confirm: true,
+            ..RunOptions::default()
+        };
+
+        Runner::new(&day, options, &mut cache).run();
+
+        assert!(
+            cache
+                .get(&CacheKey::new(1, 1, "3   4", "1"))
+                .unwrap()
+                .confirmed
+        );
+    }
+
+    #[test]
+    fn can_run_single_part() {
+        let day = example_day();
+        let mut cache = AnswerCache::new("unused.json");
+        let options = RunOptions {
+            part: Some(1),
+            ..RunOptions::default()
+        };
+
+        let results = Runner::new(&day, options, &mut cache).run();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].answer, "11");
+    }
+
+    const THRESHOLD: [Param; 1] = [Param {
+        name: "threshold",
+        description: "The threshold (default 10)",
+    }];
+
+    fn day_with_params() -> Day {
+        Day::new(2, "1", |runner| {
+            let input = runner.input();
+            let threshold = runner.param("threshold", 10);
+            runner.part(1, "Part 1: {}", || input.len() * threshold);
+        })
+        .with_params(&THRESHOLD)
+    }
+
+    #[test]
+    fn can_provide_input_and_params() {
+        let day = day_with_params();
+        let mut cache = AnswerCache::new("unused.json");
+
+        let results = Runner::new(&day, RunOptions::default(), &mut cache)
+            .with_input("abc".to_string())
+            .run();
+        assert_eq!(results[0].answer, "30");
+        assert!(results[0].cache_key.is_some());
+
+        let params = Params::from([("threshold".to_string(), "2".to_string())]);
+        let results = Runner::new(&day, RunOptions::default(), &mut cache)
+            .with_input("abc".to_string())
+            .with_params(params)
+            .run();
+        assert_eq!(results[0].answer, "6");
+        assert_eq!(results[0].cache_key, None);
+
+        // The overridden answer isn't cached, so doesn't replace the default's answer
+        assert_eq!(
+            cache
+                .get(&CacheKey::new(2, 1, "abc", "1"))
+                .map(|cached| cached.answer.as_str()),
+            Some("30")
+        );
+    }
+
+    #[test]
+    fn ignores_invalid_params() {
+        let day = day_with_params();
+        let mut cache = AnswerCache::new("unused.json");
+        let params = Params::from([("threshold".to_string(), "many".to_string())]);
+
+        let results = Runner::new(&day, RunOptions::default(), &mut cache)
+            .with_input("abc".to_string())
+            .with_params(params)
+            .run();
+
+        assert_eq!(results[0].answer, "30");
+    }
+
+    fn statuses(results: &[PartResult]) -> Vec<(u8, Status)> {
+        results
+            .iter()
+            .map(|result| (result.part, result.status))
+            .collect()
+    }
+
+    #[test]
+    fn can_run_day_in_isolation() {
+        let day = Day::new(3, "1", |runner| {
+            runner.input = Some("input".to_string());
+            runner.part(1, "Part 1: {}", || 5);
+        });
+        let mut cache = AnswerCache::new("unused.json");
+
+        let results = run_isolated(&day, RunOptions::default(), &mut cache, DEFAULT_TIMEOUT);
+
+        assert_eq!(statuses(&results), vec![(1, Status::Solved)]);
+        assert!(cache.get(&CacheKey::new(3, 1, "input", "1")).is_some());
+    }
+
+    #[test]
+    fn can_record_panicking_day() {
+        let day = Day::new(4, "1", |runner| {
+            runner.input = Some("input".to_string());
+            runner.part(1, "Part 1: {}", || 5);
+            runner.part(2, "Part 2: {}", || -> u32 { panic!("Oops") });
+        });
+        let mut cache = AnswerCache::new("unused.json");
+
+        let results = run_isolated(&day, RunOptions::default(), &mut cache, DEFAULT_TIMEOUT);
+
+        assert_eq!(
+            statuses(&results),
+            vec![(1, Status::Solved), (2, Status::Panicked)]
+        );
+        assert_eq!(results[0].answer, "5");
+        assert_eq!(results[1].answer, "");
+        assert!(cache.get(&CacheKey::new(4, 1, "input", "1")).is_some());
+        assert!(cache.get(&CacheKey::new(4, 2, "input", "1")).is_none());
+    }
+
+    #[test]
+    fn keeps_finished_parts_when_timing_out() {
+        let day = Day::new(6, "1", |runner| {
+            runner.input = Some("input".to_string());
+            runner.part(1, "Part 1: {}", || 5);
+            runner.part(2, "Part 2: {}", || {
+                thread::sleep(Duration::from_secs(2));
+                6
+            });
+        })
+        .with_timeout(Duration::from_millis(200));
+        let mut cache = AnswerCache::new("unused.json");
+
+        let results = run_isolated(&day, RunOptions::default(), &mut cache, DEFAULT_TIMEOUT);
+
+        assert_eq!(
+            statuses(&results),
+            vec![(1, Status::Solved), (2, Status::TimedOut)]
+        );
+        assert_eq!(results[0].answer, "5");
+        assert!(cache.get(&CacheKey::new(6, 1, "input", "1")).is_some());
+    }
+
+    #[test]
+    fn can_time_out_day() {
+        let day = Day::new(5, "1", |runner| {
+            runner.part(1, "Part 1: {}", || {
+                thread::sleep(Duration::from_secs(2));
+                5
+            });
+        })
+        .with_timeout(Duration::from_millis(50));
+        let options = RunOptions {
+            part: Some(1),
+            ..RunOptions::default()
+        };
+        let mut cache = AnswerCache::new("unused.json");
+
+        let start = Instant::now();
+        let results = run_isolated(&day, options, &mut cache, DEFAULT_TIMEOUT);
+
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(statuses(&results), vec![(1, Status::TimedOut)]);
+        assert_eq!(results[0].answer, "");
+    }
+}
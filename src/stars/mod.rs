@@ -0,0 +1,144 @@
+//! The stars earned on the Advent of Code site for each day, as read from the event's calendar page by
+//! `--stars`, and cached locally so that they can be shown without going online.
+//!
+//! Comparing them with the registered solutions and the [`crate::answer_cache`] flags days that look out of step, e.g.
+//! a solution that has never been submitted, or stars for a part with no stored answer for the current input.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// The default location of the cached stars
+pub const STARS_PATH: &str = "res/stars.json";
+
+/// The number of stars for each day that has unlocked, and the file it is persisted to
+#[derive(Eq, PartialEq, Debug)]
+pub struct Stars {
+    path: PathBuf,
+    days: BTreeMap<u8, u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StarsFile {
+    days: BTreeMap<u8, u8>,
+}
+
+impl Stars {
+    /// No known stars, that will be saved to `path`
+    pub fn new(path: impl AsRef<Path>) -> Stars {
+        Stars {
+            path: path.as_ref().to_path_buf(),
+            days: BTreeMap::new(),
+        }
+    }
+
+    /// Load the stars from `path`, a missing file is treated as the stars never having been fetched
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Stars> {
+        let mut stars = Stars::new(path);
+        match fs::read_to_string(&stars.path) {
+            Ok(contents) => {
+                stars.days = serde_json::from_str::<StarsFile>(&contents)?.days;
+                Ok(stars)
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(stars),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Write the stars back to the file they were loaded from
+    #[cfg_attr(not(feature = "network"), allow(dead_code))]
+    pub fn save(&self) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = StarsFile {
+            days: self.days.clone(),
+        };
+        fs::write(&self.path, serde_json::to_string_pretty(&file)?)
+    }
+
+    /// The stars for `day`, or None if it hadn't unlocked when the stars were fetched
+    pub fn get(&self, day: u8) -> Option<u8> {
+        self.days.get(&day).copied()
+    }
+
+    /// Replace the stars with those freshly read from the calendar
+    #[cfg_attr(not(feature = "network"), allow(dead_code))]
+    pub fn replace(&mut self, days: BTreeMap<u8, u8>) {
+        self.days = days;
+    }
+}
+
+/// A day where the stars on the site don't match what is known locally
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum StarFlag {
+    /// There is a solution registered, but no stars have been earned with it
+    NoStars,
+    /// The part has a star, but there is no stored answer for it with the current input
+    NoAnswer { part: u8 },
+}
+
+impl Display for StarFlag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StarFlag::NoStars => write!(f, "solved, no stars"),
+            StarFlag::NoAnswer { part } => write!(f, "no part {part} answer"),
+        }
+    }
+}
+
+/// Check a day's stars against whether it has a solution, and which parts have a stored answer
+pub fn flag(solved: bool, stars: u8, answers: [bool; 2]) -> Option<StarFlag> {
+    if solved && stars == 0 {
+        return Some(StarFlag::NoStars);
+    }
+
+    (1..=stars.min(2))
+        .find(|&part| !answers[part as usize - 1])
+        .map(|part| StarFlag::NoAnswer { part })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stars::*;
+    use std::env::temp_dir;
+
+    #[test]
+    fn can_flag_mismatched_stars() {
+        assert_eq!(flag(true, 2, [true, true]), None);
+        assert_eq!(flag(false, 0, [false, false]), None);
+        assert_eq!(flag(true, 0, [true, true]), Some(StarFlag::NoStars));
+        assert_eq!(
+            flag(false, 1, [false, false]),
+            Some(StarFlag::NoAnswer { part: 1 })
+        );
+        assert_eq!(
+            flag(true, 2, [true, false]),
+            Some(StarFlag::NoAnswer { part: 2 })
+        );
+        assert_eq!(flag(true, 1, [true, false]), None);
+    }
+
+    #[test]
+    fn can_save_and_load_stars() {
+        let path = temp_dir().join(format!("aoc-stars-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(Stars::load(&path).unwrap(), Stars::new(&path));
+
+        let mut stars = Stars::new(&path);
+        stars.replace(BTreeMap::from([(1, 2), (2, 1), (3, 0)]));
+        stars.save().unwrap();
+
+        let loaded = Stars::load(&path).unwrap();
+        assert_eq!(loaded, stars);
+        assert_eq!(loaded.get(1), Some(2));
+        assert_eq!(loaded.get(3), Some(0));
+        assert_eq!(loaded.get(4), None);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,67 @@
+//! Configures the [`tracing`] subscriber used for diagnosing misbehaving solutions.
+//!
+//! [`crate::runner::Runner`] opens a `day` span for each day, and a `part` span for each part within it. Solutions
+//! emit events within those spans, e.g. heap pops in [`crate::day_16`], cache hits in [`crate::day_21`] and loop
+//! detection in [`crate::day_6`]. Tracing is off unless a level is requested, and can be restricted to a single day
+//! using the `day` span's field. Events are written to stderr, or as JSON lines to a file, so that they don't get mixed
+//! up with the answers on stdout.
+
+use std::fs::File;
+use std::path::Path;
+use std::sync::Mutex;
+use tracing::Level;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::EnvFilter;
+
+/// Build the filter directive for events at `level` from this crate, optionally only within spans for `day`. Both the
+/// `day` and `part` spans have a `day` field, so events in rayon workers that only re-enter the `part` span still
+/// match.
+fn filter_directive(level: Level, day: Option<u8>) -> String {
+    let target = env!("CARGO_CRATE_NAME");
+    match day {
+        Some(day) => format!("{target}[{{day={day}}}]={level}"),
+        None => format!("{target}={level}"),
+    }
+}
+
+/// Install the global subscriber. If `level` is `None` no subscriber is installed, and all trace events are
+/// discarded.
+pub fn init(level: Option<Level>, day: Option<u8>, file: Option<&Path>) -> std::io::Result<()> {
+    let Some(level) = level else {
+        return Ok(());
+    };
+
+    let filter = EnvFilter::new(filter_directive(level, day));
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE);
+
+    match file {
+        Some(path) => builder
+            .json()
+            .with_writer(Mutex::new(File::create(path)?))
+            .init(),
+        None => builder.with_writer(std::io::stderr).init(),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trace::*;
+
+    #[test]
+    fn can_build_filter_directive() {
+        assert_eq!(
+            filter_directive(Level::DEBUG, None),
+            "advent_of_code_2024=DEBUG"
+        );
+        assert_eq!(
+            filter_directive(Level::TRACE, Some(16)),
+            "advent_of_code_2024[{day=16}]=TRACE"
+        );
+
+        assert!(EnvFilter::try_new(filter_directive(Level::TRACE, Some(16))).is_ok());
+    }
+}
@@ -0,0 +1,264 @@
+//! Watch mode for working on a single day. When the day's source file, its fixtures, or its input change, the project
+//! is rebuilt, the day's tests are run, and the day is re-run against the real input. Each cycle prints a compact
+//! summary of the test results and the answers.
+//!
+//! Files are polled for changes rather than using filesystem notifications, as there are only a handful to watch. A
+//! day's fixtures are any files in `res/` prefixed with `day-N-`, which includes the puzzle input.
+//!
+//! The rebuilds and runs are done by invoking `cargo` as a subprocess, so that changes to the source are picked up.
+//! The real input is always run with `--force`, as the source changing doesn't invalidate cached answers.
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread::sleep;
+use std::time::{Duration, SystemTime};
+
+/// How often to check for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The modification time of each watched file that currently exists
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Counts parsed from the `test result:` line(s) of `cargo test` output
+#[derive(Eq, PartialEq, Debug, Default, Copy, Clone)]
+struct TestSummary {
+    passed: usize,
+    failed: usize,
+}
+
+/// List the files to watch for `day`, relative to `root`
+fn watched_paths(root: &Path, day: u8) -> Vec<PathBuf> {
+    let mut paths = vec![root.join(format!("src/day_{day}.rs"))];
+    let prefix = format!("day-{day}-");
+
+    if let Ok(entries) = fs::read_dir(root.join("res")) {
+        let mut fixtures: Vec<PathBuf> = entries
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+            .map(|entry| entry.path())
+            .collect();
+        fixtures.sort();
+        paths.extend(fixtures);
+    }
+
+    paths
+}
+
+/// Record the modification times of the watched files. New and deleted fixtures are picked up as the directory is
+/// re-read each time.
+fn snapshot(root: &Path, day: u8) -> Snapshot {
+    watched_paths(root, day)
+        .into_iter()
+        .flat_map(|path| {
+            let modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok();
+            modified.map(|modified| (path, modified))
+        })
+        .collect()
+}
+
+/// Sum the counts from each `test result:` line in the output of `cargo test`, e.g.
+///
+/// ```text
+/// test result: ok. 5 passed; 0 failed; 0 ignored; 0 measured; 126 filtered out; finished in 0.03s
+/// ```
+fn parse_test_summary(output: &str) -> Option<TestSummary> {
+    let lines: Vec<&str> = output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("test result: "))
+        .collect();
+
+    if lines.is_empty() {
+        return None;
+    }
+
+    let mut summary = TestSummary::default();
+    for line in lines {
+        for part in line.split([';', '.']) {
+            match part.trim().split_once(' ') {
+                Some((count, "passed")) => summary.passed += count.parse::<usize>().unwrap_or(0),
+                Some((count, "failed")) => summary.failed += count.parse::<usize>().unwrap_or(0),
+                _ => (),
+            }
+        }
+    }
+
+    Some(summary)
+}
+
+/// Format the `--format json` output of a run as one line per part
+fn format_answers(output: &str) -> Vec<String> {
+    let records: Vec<Value> = serde_json::from_str(output).unwrap_or_default();
+    records
+        .iter()
+        .map(|record| {
+            format!(
+                "  Part {}: {} ({:.2}ms)",
+                record["part"],
+                record["answer"].as_str().unwrap_or("?"),
+                record["duration_ms"].as_f64().unwrap_or(0.0)
+            )
+        })
+        .collect()
+}
+
+/// Rebuild and run the day's tests, printing a one line summary
+fn run_tests(day: u8) {
+    let output = Command::new("cargo")
+        .args(["test", "--quiet", &format!("day_{day}::")])
+        .output();
+
+    match output {
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            match parse_test_summary(&stdout) {
+                Some(TestSummary { passed, failed: 0 }) => println!("PASS  {passed} tests passed"),
+                Some(TestSummary { passed, failed }) => {
+                    println!("FAIL  {failed} failed, {passed} passed");
+                    stdout
+                        .lines()
+                        .filter(|line| line.starts_with("---- ") || line.contains("panicked at"))
+                        .for_each(|line| println!("  {line}"));
+                }
+                None => {
+                    println!("FAIL  build failed");
+                    String::from_utf8_lossy(&output.stderr)
+                        .lines()
+                        .filter(|line| line.starts_with("error"))
+                        .for_each(|line| println!("  {line}"));
+                }
+            }
+        }
+        Err(e) => println!("FAIL  could not run cargo: {e}"),
+    }
+}
+
+/// Run the day against the real input, printing the answers
+fn run_day(day: u8) {
+    let output = Command::new("cargo")
+        .args(["run", "--quiet", "--", &day.to_string()])
+        .args(["--format", "json", "--force"])
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            format_answers(&String::from_utf8_lossy(&output.stdout))
+                .iter()
+                .for_each(|line| println!("{line}"))
+        }
+        Ok(output) => {
+            println!("  Run failed");
+            String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .filter(|line| line.contains("panicked at") || line.starts_with("error"))
+                .for_each(|line| println!("  {line}"));
+        }
+        Err(e) => println!("  Could not run cargo: {e}"),
+    }
+}
+
+/// Watch the files for `day`, running the tests and the real input at the start and then every time they change.
+/// This runs until the process is interrupted.
+pub fn watch(day: u8) {
+    let root = PathBuf::from(".");
+    let mut previous = Snapshot::new();
+
+    loop {
+        let current = snapshot(&root, day);
+        if current != previous {
+            println!("==== Day {day} ====");
+            run_tests(day);
+            run_day(day);
+            println!("Watching for changes...");
+
+            // Compare against the snapshot from before the run, so that edits made while it was running trigger
+            // another run
+            previous = current;
+        }
+
+        sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::watch::*;
+    use std::env::temp_dir;
+
+    #[test]
+    fn can_list_watched_paths() {
+        let root = temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(root.join("res")).unwrap();
+        for file in [
+            "day-1-input.txt",
+            "day-1-example.txt",
+            "day-10-input.txt",
+            "session_cookie.txt",
+        ] {
+            fs::write(root.join("res").join(file), "").unwrap();
+        }
+
+        assert_eq!(
+            watched_paths(&root, 1),
+            vec![
+                root.join("src/day_1.rs"),
+                root.join("res/day-1-example.txt"),
+                root.join("res/day-1-input.txt"),
+            ]
+        );
+
+        let snapshot = snapshot(&root, 1);
+        assert_eq!(
+            snapshot.keys().collect::<Vec<_>>(),
+            vec![
+                &root.join("res/day-1-example.txt"),
+                &root.join("res/day-1-input.txt"),
+            ]
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn can_parse_test_summary() {
+        assert_eq!(parse_test_summary("error[E0425]: cannot find value"), None);
+        assert_eq!(
+            parse_test_summary(
+                "running 5 tests
+.....
+test result: ok. 5 passed; 0 failed; 0 ignored; 0 measured; 126 filtered out; finished in 0.03s
+"
+            ),
+            Some(TestSummary {
+                passed: 5,
+                failed: 0
+            })
+        );
+        assert_eq!(
+            parse_test_summary(
+                "test result: FAILED. 3 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.03s
+test result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s"
+            ),
+            Some(TestSummary {
+                passed: 4,
+                failed: 2
+            })
+        );
+    }
+
+    #[test]
+    fn can_format_answers() {
+        assert_eq!(
+            format_answers(
+                r#"[
+                {"day": 1, "part": 1, "answer": "11", "duration_ms": 0.0375, "status": "solved"},
+                {"day": 1, "part": 2, "answer": "31", "duration_ms": 1.5, "status": "solved"}
+            ]"#
+            ),
+            vec!["  Part 1: 11 (0.04ms)", "  Part 2: 31 (1.50ms)"]
+        );
+        assert!(format_answers("not json").is_empty());
+    }
+}
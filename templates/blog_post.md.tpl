@@ -0,0 +1,5 @@
+---
+day: {{day}}
+tags: [post]
+header: 'Day {{day}}: {{title}}'
+---
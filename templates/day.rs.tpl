@@ -0,0 +1,23 @@
+//! This is my solution for [Advent of Code - Day {{day}}: _{{title}}_]({{puzzle_url}})
+//!
+//!
+
+use crate::runner::Runner;
+
+/// The entry point for running the solutions with the 'real' puzzle input.
+///
+/// - The puzzle input is expected to be at `<project_root>/{{input_path}}`
+/// - It is expected this will be called by [`super::main()`] when the user elects to run day {{day}}.
+pub fn run(runner: &mut Runner) {
+    let contents = runner.input();
+    let _parsed = parse_input(&contents);
+}
+
+{{parse_input}}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_{{day}}::*;
+
+{{parse_test}}
+}